@@ -0,0 +1,258 @@
+use crate::utils::diagnostics::{
+    Diagnostic, Severity, compiler_error_diagnostics, line_and_column,
+};
+use crate::utils::errors::{CommandError, EXIT_CODE_COMPILER_ERROR, EXIT_CODE_ERROR};
+use crate::utils::files::collect_dx_files;
+use crate::utils::output::{print_error, print_warning};
+use clap::ValueEnum;
+use datex_core::ast::lexer::Token;
+use datex_core::compiler::compile_template;
+use datex_core::values::value::Value;
+use datex_core::values::value_container::ValueContainer;
+use logos::Logos;
+use serde::Serialize;
+use std::fmt::Display;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Output format for the diagnostics reported by `datex check`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum CheckFormat {
+    /// one line per diagnostic on stderr
+    #[default]
+    Human,
+    /// a single JSON report on stdout
+    Json,
+}
+
+#[derive(Debug, Clone)]
+pub struct CheckOptions {
+    /// files or directories to check
+    pub paths: Vec<PathBuf>,
+    pub format: CheckFormat,
+}
+
+#[derive(Debug)]
+pub enum CheckError {
+    IOError(std::io::Error),
+    JsonError(serde_json::Error),
+    /// number of errors and number of checked files
+    CompilerErrors {
+        errors: usize,
+        files: usize,
+    },
+    /// number of files that could not be read, number of errors in the other files
+    /// and number of checked files
+    UnreadableFiles {
+        unreadable: usize,
+        errors: usize,
+        files: usize,
+    },
+}
+
+impl CommandError for CheckError {
+    fn exit_code(&self) -> i32 {
+        match self {
+            CheckError::CompilerErrors { .. } => EXIT_CODE_COMPILER_ERROR,
+            _ => EXIT_CODE_ERROR,
+        }
+    }
+}
+
+impl Display for CheckError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CheckError::IOError(e) => write!(f, "[IO Error] {e}"),
+            CheckError::JsonError(e) => write!(f, "[Output Error] {e}"),
+            CheckError::CompilerErrors { errors, files } => {
+                write!(f, "Found {errors} error(s) in {files} checked file(s)")
+            }
+            CheckError::UnreadableFiles {
+                unreadable,
+                errors,
+                files,
+            } => write!(
+                f,
+                "Could not read {unreadable} of {files} file(s), found {errors} error(s) in the others"
+            ),
+        }
+    }
+}
+
+impl From<std::io::Error> for CheckError {
+    fn from(err: std::io::Error) -> Self {
+        CheckError::IOError(err)
+    }
+}
+impl From<serde_json::Error> for CheckError {
+    fn from(err: serde_json::Error) -> Self {
+        CheckError::JsonError(err)
+    }
+}
+
+/// Report printed in the JSON format
+#[derive(Serialize)]
+struct CheckReport<'a> {
+    files: usize,
+    errors: usize,
+    warnings: usize,
+    diagnostics: &'a [Diagnostic],
+}
+
+/// Compiles a single script without executing it and returns its diagnostics.
+fn check_source(path: &Path, source: &str) -> Vec<Diagnostic> {
+    let mut diagnostics = vec![];
+
+    // placeholders can only be filled when the script is executed with arguments,
+    // so they are compiled with null values instead
+    let placeholders = Token::lexer(source)
+        .spanned()
+        .filter(|(token, _)| matches!(token, Ok(Token::Placeholder)))
+        .map(|(_, span)| span.start)
+        .collect::<Vec<_>>();
+    for offset in &placeholders {
+        diagnostics.push(Diagnostic {
+            severity: Severity::Warning,
+            path: path.to_path_buf(),
+            position: Some(line_and_column(source, *offset)),
+            message: "placeholder '?' must be provided as an argument when the script is run"
+                .to_string(),
+        });
+    }
+    let inserted_values = vec![ValueContainer::from(Value::null()); placeholders.len()];
+
+    if let Err(e) = compile_template(source, &inserted_values, Default::default()) {
+        diagnostics.extend(compiler_error_diagnostics(&e, path, source));
+    }
+    diagnostics
+}
+
+/// Compiles all `.dx` files in the given paths without executing them
+/// and reports all compiler errors and warnings.
+/// No runtime is created, so checked scripts can not have any side effects.
+pub fn check(options: CheckOptions) -> Result<(), CheckError> {
+    let paths = if options.paths.is_empty() {
+        vec![PathBuf::from(".")]
+    } else {
+        options.paths
+    };
+
+    let files = collect_dx_files(&paths)?;
+    let mut diagnostics = vec![];
+    // files that can not be read are reported, but do not stop checking the other files
+    let mut read_errors = vec![];
+    for path in &files {
+        match fs::read_to_string(path) {
+            Ok(source) => diagnostics.extend(check_source(path, &source)),
+            Err(e) => read_errors.push(Diagnostic {
+                severity: Severity::Error,
+                path: path.clone(),
+                position: None,
+                message: format!("could not read file: {e}"),
+            }),
+        }
+    }
+
+    let errors = diagnostics
+        .iter()
+        .filter(|d| d.severity == Severity::Error)
+        .count();
+    let warnings = diagnostics.len() - errors;
+
+    match options.format {
+        CheckFormat::Human => {
+            for diagnostic in &read_errors {
+                print_error(&format!("[IO Error] {diagnostic}"));
+            }
+            for diagnostic in &diagnostics {
+                match diagnostic.severity {
+                    Severity::Error => print_error(&format!("[Compiler Error] {diagnostic}")),
+                    Severity::Warning => print_warning(&format!("[Warning] {diagnostic}")),
+                }
+            }
+            if errors == 0 && read_errors.is_empty() {
+                println!(
+                    "Checked {} file(s): no errors, {warnings} warning(s)",
+                    files.len()
+                );
+            }
+        }
+        CheckFormat::Json => {
+            let diagnostics = read_errors
+                .iter()
+                .chain(&diagnostics)
+                .cloned()
+                .collect::<Vec<_>>();
+            let report = CheckReport {
+                files: files.len(),
+                errors: errors + read_errors.len(),
+                warnings,
+                diagnostics: &diagnostics,
+            };
+            println!("{}", serde_json::to_string_pretty(&report)?);
+        }
+    }
+
+    if !read_errors.is_empty() {
+        Err(CheckError::UnreadableFiles {
+            unreadable: read_errors.len(),
+            errors,
+            files: files.len(),
+        })
+    } else if errors > 0 {
+        Err(CheckError::CompilerErrors {
+            errors,
+            files: files.len(),
+        })
+    } else {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::diagnostics::Position;
+
+    fn check(source: &str) -> Vec<Diagnostic> {
+        check_source(Path::new("test.dx"), source)
+    }
+
+    #[test]
+    fn accepts_valid_source() {
+        assert!(check("{a: 1, b: [1, 2]}").is_empty());
+        assert!(check("const x = 1;\nx + 2").is_empty());
+    }
+
+    #[test]
+    fn reports_errors_with_line_and_column() {
+        let diagnostics = check("const x = 1;\nx + )");
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].severity, Severity::Error);
+        assert_eq!(diagnostics[0].path, PathBuf::from("test.dx"));
+        assert_eq!(
+            diagnostics[0].position,
+            Some(Position { line: 2, column: 5 })
+        );
+    }
+
+    #[test]
+    fn reports_placeholders_as_warnings() {
+        let diagnostics = check("1 +\n  ?");
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].severity, Severity::Warning);
+        assert_eq!(
+            diagnostics[0].position,
+            Some(Position { line: 2, column: 3 })
+        );
+    }
+
+    #[test]
+    fn counts_positions_after_a_shebang_line() {
+        let diagnostics = check("#!/usr/bin/env datex\n1 + )");
+        assert_eq!(
+            diagnostics[0].position,
+            Some(Position { line: 2, column: 5 })
+        );
+    }
+}
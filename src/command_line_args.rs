@@ -1,5 +1,9 @@
-use std::path::PathBuf;
+use crate::check::CheckFormat;
+use crate::convert::ConvertFormat;
+use crate::highlight::HighlightFormat;
+use crate::utils::output::OutputFormat;
 use clap::{Args, Parser, Subcommand};
+use std::path::PathBuf;
 
 #[derive(Parser)]
 #[command(author, version, about, long_about = None, bin_name = "datex")]
@@ -12,19 +16,157 @@ pub struct Cli {
 #[derive(Subcommand)]
 pub enum Subcommands {
     Run(Run),
+    Check(Check),
+    Compile(Compile),
+    Convert(Convert),
+    Decompile(Decompile),
+    Diff(Diff),
+    Disasm(Disasm),
+    Fmt(Fmt),
+    Highlight(Highlight),
+    Query(Query),
     Lsp(Lsp),
     Repl(Repl),
     Workbench(Workbench),
 }
 
+/// Execute a DATEX script file, an inline expression or a script from stdin
 #[derive(Args)]
 pub struct Run {
-    pub file: Option<String>,
+    /// path to the DATEX script to execute, or `-` to read the script from stdin
+    #[arg(required_unless_present = "eval", conflicts_with = "eval")]
+    pub file: Option<PathBuf>,
+    /// DATEX expression to evaluate instead of a file
+    #[arg(short, long, value_name = "EXPRESSION")]
+    pub eval: Option<String>,
+    /// named argument passed to the script as a constant, in the form `name=value`.
+    /// Words that are not valid DATEX are passed as text
+    #[arg(long = "arg", value_name = "NAME=VALUE")]
+    pub named_args: Vec<String>,
+    /// Re-execute the script whenever the file changes.
+    /// Only the script file itself is watched, DATEX has no imports yet
+    #[arg(short, long, conflicts_with = "eval")]
+    pub watch: bool,
+    /// DATEX values inserted for the `?` placeholders in the script.
+    /// Words that are not valid DATEX are passed as text
+    #[arg(last = true, value_name = "ARGS")]
+    pub args: Vec<String>,
+    /// Verbose mode for debugging
+    #[arg(short, long)]
+    pub verbose: bool,
+    /// optional path to dx config file
+    #[arg(short, long)]
+    pub config: Option<PathBuf>,
+    /// output format for result values
+    #[arg(short, long, value_enum, default_value_t = OutputFormat::Dx)]
+    pub output: OutputFormat,
+}
+
+/// Compile DATEX scripts without executing them and report all errors and warnings
+#[derive(Args)]
+pub struct Check {
+    /// files or directories to check, defaults to the current directory
+    pub paths: Vec<PathBuf>,
+    /// output format for the diagnostics
+    #[arg(long, value_enum, default_value_t = CheckFormat::Human)]
+    pub format: CheckFormat,
+}
+
+/// Compile a DATEX script into a DXB bytecode file without executing it
+#[derive(Args)]
+pub struct Compile {
+    /// path to the DATEX script to compile
+    pub file: PathBuf,
+    /// path of the output file, defaults to the input file with a `.dxb` extension
+    #[arg(short, long)]
+    pub output: Option<PathBuf>,
+}
+
+/// Convert between JSON documents and static DATEX values
+#[derive(Args)]
+pub struct Convert {
+    /// path to the input file, or `-` to read from stdin
+    pub file: PathBuf,
+    /// format of the input
+    #[arg(long, value_enum)]
+    pub from: ConvertFormat,
+    /// format of the output
+    #[arg(long, value_enum)]
+    pub to: ConvertFormat,
+    /// path of the output file, the result is printed to stdout if not set
+    #[arg(short, long)]
+    pub output: Option<PathBuf>,
+}
+
+/// Decompile a DXB bytecode file into DATEX source code
+#[derive(Args)]
+pub struct Decompile {
+    /// path to the DXB file, or `-` to read a hex string from stdin
+    pub file: PathBuf,
+    /// print the decompiled script on a single line instead of multiline
+    #[arg(long)]
+    pub compact: bool,
+    /// disable syntax highlighting
+    #[arg(long)]
+    pub plain: bool,
+}
+
+/// Print a structural diff between the values of two DATEX, DXB or JSON files
+#[derive(Args)]
+pub struct Diff {
+    /// path to the old `.dx`, `.dxb` or `.json` file
+    pub old: PathBuf,
+    /// path to the new `.dx`, `.dxb` or `.json` file
+    pub new: PathBuf,
+}
+
+/// Print an annotated DXB instruction listing for a DATEX script or DXB file
+///
+/// Each instruction is listed with its byte offset, raw bytes, opcode name and operands.
+/// Source spans are not shown, since the compiler does not record source positions.
+#[derive(Args)]
+pub struct Disasm {
+    /// path to a DATEX script (`.dx`) or DXB file, or `-` to read a hex string from stdin
+    pub file: PathBuf,
+}
+
+/// Format DATEX files in the canonical multiline style
+#[derive(Args)]
+pub struct Fmt {
+    /// files or directories to format, defaults to the current directory
+    pub paths: Vec<PathBuf>,
+    /// check if the files are formatted without writing them
+    #[arg(long)]
+    pub check: bool,
+}
+
+/// Print a syntax highlighted DATEX script as ANSI or HTML
+#[derive(Args)]
+pub struct Highlight {
+    /// path to the DATEX script, or `-` to read from stdin
+    pub file: PathBuf,
+    /// output format
+    #[arg(long, value_enum, default_value_t = HighlightFormat::Ansi)]
+    pub format: HighlightFormat,
+}
+
+/// Extract or filter nested fields of a DATEX or JSON value with a jq-style query
+#[derive(Args)]
+pub struct Query {
+    /// path to a `.dx`, `.dxb` or `.json` file, or `-` to read from stdin
+    pub file: PathBuf,
+    /// query, e.g. `.users[] | select(.age > 30) | .name`
+    pub query: String,
+    /// output format for result values
+    #[arg(short, long, value_enum, default_value_t = OutputFormat::Dx)]
+    pub output: OutputFormat,
 }
 
+/// Start the DATEX language server on stdin and stdout
 #[derive(Args)]
 pub struct Lsp {}
 
+/// Start an interactive DATEX REPL (default if no command is given)
 #[derive(Args)]
 pub struct Repl {
     /// Verbose mode for debugging
@@ -33,8 +175,12 @@ pub struct Repl {
     /// optional path to dx config file
     #[arg(short, long)]
     pub config: Option<PathBuf>,
+    /// output format for result values
+    #[arg(short, long, value_enum, default_value_t = OutputFormat::Dx)]
+    pub output: OutputFormat,
 }
 
+/// Open the terminal workbench to inspect the runtime
 #[derive(Args)]
 pub struct Workbench {}
 
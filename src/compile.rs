@@ -0,0 +1,69 @@
+use crate::utils::diagnostics::{Diagnostic, compiler_error_diagnostics, format_compiler_errors};
+use crate::utils::errors::{CommandError, EXIT_CODE_COMPILER_ERROR, EXIT_CODE_ERROR};
+use datex_core::compiler::compile_script;
+use std::fmt::Display;
+use std::fs;
+use std::path::PathBuf;
+
+#[derive(Debug, Clone)]
+pub struct CompileOptions {
+    pub file: PathBuf,
+    /// path of the output file, defaults to the input file with a `.dxb` extension
+    pub output: Option<PathBuf>,
+}
+
+#[derive(Debug)]
+pub enum CompileError {
+    IOError(std::io::Error),
+    CompilerError(Vec<Diagnostic>),
+}
+
+impl CommandError for CompileError {
+    fn exit_code(&self) -> i32 {
+        match self {
+            CompileError::IOError(_) => EXIT_CODE_ERROR,
+            CompileError::CompilerError(_) => EXIT_CODE_COMPILER_ERROR,
+        }
+    }
+}
+
+impl Display for CompileError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CompileError::IOError(e) => write!(f, "[IO Error] {e}"),
+            CompileError::CompilerError(diagnostics) => {
+                write!(f, "{}", format_compiler_errors(diagnostics))
+            }
+        }
+    }
+}
+
+impl From<std::io::Error> for CompileError {
+    fn from(err: std::io::Error) -> Self {
+        CompileError::IOError(err)
+    }
+}
+
+/// Compiles a DATEX script file into a DXB body without executing it
+/// and writes the bytecode to the output file.
+pub fn compile(options: CompileOptions) -> Result<(), CompileError> {
+    let script = fs::read_to_string(&options.file)?;
+
+    let (dxb, _) = compile_script(&script, Default::default()).map_err(|e| {
+        CompileError::CompilerError(compiler_error_diagnostics(&e, &options.file, &script))
+    })?;
+
+    let output = options
+        .output
+        .unwrap_or_else(|| options.file.with_extension("dxb"));
+    fs::write(&output, &dxb)?;
+
+    println!(
+        "Compiled {} to {} ({} bytes)",
+        options.file.display(),
+        output.display(),
+        dxb.len()
+    );
+
+    Ok(())
+}
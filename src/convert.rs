@@ -0,0 +1,159 @@
+use crate::utils::diagnostics::{Diagnostic, compiler_error_diagnostics, format_compiler_errors};
+use crate::utils::errors::{CommandError, EXIT_CODE_COMPILER_ERROR, EXIT_CODE_ERROR};
+use crate::utils::json::json_to_value;
+use crate::utils::output::{OutputError, OutputFormat, canonical_source, format_value};
+use clap::ValueEnum;
+use datex_core::compiler::error::CompilerError;
+use datex_core::compiler::extract_static_value_from_script;
+use datex_core::values::value_container::ValueContainer;
+use std::fmt::Display;
+use std::fs;
+use std::path::PathBuf;
+
+/// Data format that can be converted by `datex convert`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ConvertFormat {
+    /// JSON document
+    Json,
+    /// static DATEX value
+    Dx,
+}
+
+#[derive(Debug, Clone)]
+pub struct ConvertOptions {
+    /// path to the input file, or `-` to read from stdin
+    pub file: PathBuf,
+    pub from: ConvertFormat,
+    pub to: ConvertFormat,
+    /// path of the output file, the result is printed to stdout if not set
+    pub output: Option<PathBuf>,
+}
+
+#[derive(Debug)]
+pub enum ConvertError {
+    IOError(std::io::Error),
+    JsonError(serde_json::Error),
+    CompilerError(Vec<Diagnostic>),
+    NonStaticValue,
+    OutputError(OutputError),
+}
+
+impl CommandError for ConvertError {
+    fn exit_code(&self) -> i32 {
+        match self {
+            ConvertError::CompilerError(_) => EXIT_CODE_COMPILER_ERROR,
+            _ => EXIT_CODE_ERROR,
+        }
+    }
+}
+
+impl Display for ConvertError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConvertError::IOError(e) => write!(f, "[IO Error] {e}"),
+            ConvertError::JsonError(e) => write!(f, "[JSON Error] {e}"),
+            ConvertError::CompilerError(diagnostics) => {
+                write!(f, "{}", format_compiler_errors(diagnostics))
+            }
+            ConvertError::NonStaticValue => {
+                write!(f, "[Compiler Error] only static values can be converted")
+            }
+            ConvertError::OutputError(e) => write!(f, "[Output Error] {e}"),
+        }
+    }
+}
+
+impl From<std::io::Error> for ConvertError {
+    fn from(err: std::io::Error) -> Self {
+        ConvertError::IOError(err)
+    }
+}
+impl From<serde_json::Error> for ConvertError {
+    fn from(err: serde_json::Error) -> Self {
+        ConvertError::JsonError(err)
+    }
+}
+impl From<OutputError> for ConvertError {
+    fn from(err: OutputError) -> Self {
+        ConvertError::OutputError(err)
+    }
+}
+
+/// Parses the input in the given format into a DATEX value
+fn parse_input(options: &ConvertOptions, input: &str) -> Result<ValueContainer, ConvertError> {
+    match options.from {
+        ConvertFormat::Json => {
+            let json: serde_json::Value = serde_json::from_str(input)?;
+            Ok(json_to_value(&json))
+        }
+        ConvertFormat::Dx => match extract_static_value_from_script(input) {
+            Ok(Some(value)) => Ok(value),
+            Ok(None) | Err(CompilerError::NonStaticValue) => Err(ConvertError::NonStaticValue),
+            Err(e) => Err(ConvertError::CompilerError(compiler_error_diagnostics(
+                &e,
+                &options.file,
+                input,
+            ))),
+        },
+    }
+}
+
+/// Converts the input into the output format
+fn convert_input(options: &ConvertOptions, input: &str) -> Result<String, ConvertError> {
+    let value = parse_input(options, input)?;
+    Ok(match options.to {
+        ConvertFormat::Dx => canonical_source(&value),
+        ConvertFormat::Json => format!("{}\n", format_value(&value, OutputFormat::Json)?),
+    })
+}
+
+/// Converts a JSON document into a DATEX value or a static DATEX value into JSON.
+pub fn convert(options: ConvertOptions) -> Result<(), ConvertError> {
+    let input = if options.file.as_os_str() == "-" {
+        std::io::read_to_string(std::io::stdin())?
+    } else {
+        fs::read_to_string(&options.file)?
+    };
+
+    let converted = convert_input(&options, &input)?;
+
+    match options.output {
+        Some(output) => fs::write(output, converted)?,
+        None => print!("{converted}"),
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn convert_string(input: &str, from: ConvertFormat, to: ConvertFormat) -> String {
+        let options = ConvertOptions {
+            file: PathBuf::from("test"),
+            from,
+            to,
+            output: None,
+        };
+        convert_input(&options, input).unwrap()
+    }
+
+    #[test]
+    fn keeps_json_key_order() {
+        let dx = convert_string(r#"{"b":1,"a":2}"#, ConvertFormat::Json, ConvertFormat::Dx);
+        assert_eq!(dx, "{b: 1, a: 2}\n");
+        let json = convert_string(&dx, ConvertFormat::Dx, ConvertFormat::Json);
+        assert_eq!(json, "{\n  \"b\": 1,\n  \"a\": 2\n}\n");
+    }
+
+    #[test]
+    fn converts_json_literals() {
+        let dx = convert_string(
+            r#"[null, true, 1, 1.5, "text"]"#,
+            ConvertFormat::Json,
+            ConvertFormat::Dx,
+        );
+        assert_eq!(dx, "[null, true, 1, 1.5, \"text\"]\n");
+    }
+}
@@ -0,0 +1,37 @@
+use crate::utils::dxb::{DecompileError, read_dxb_body};
+use datex_core::decompiler::{self, Formatting, decompile_body};
+use std::io::IsTerminal;
+use std::path::PathBuf;
+
+#[derive(Debug, Clone)]
+pub struct DecompileOptions {
+    /// path to the DXB file, or `-` to read a hex string from stdin
+    pub file: PathBuf,
+    /// print the decompiled script on a single line
+    pub compact: bool,
+    /// disable syntax highlighting
+    pub plain: bool,
+}
+
+/// Decompiles a DXB file or hex string and prints it as DATEX source code.
+pub fn decompile(options: DecompileOptions) -> Result<(), DecompileError> {
+    let body = read_dxb_body(&options.file)?;
+
+    let source = decompile_body(
+        &body,
+        decompiler::DecompileOptions {
+            formatting: if options.compact {
+                Formatting::Compact
+            } else {
+                Formatting::multiline()
+            },
+            colorized: !options.plain && std::io::stdout().is_terminal(),
+            resolve_slots: true,
+            ..decompiler::DecompileOptions::default()
+        },
+    )
+    .map_err(|e| DecompileError::DecompilerError(e.to_string()))?;
+    println!("{source}");
+
+    Ok(())
+}
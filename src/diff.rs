@@ -0,0 +1,231 @@
+use crate::utils::errors::{CommandError, EXIT_CODE_ERROR};
+use crate::utils::output::compact_source;
+use crate::utils::static_value::{LoadError, load_static_value};
+use datex_core::values::core_value::CoreValue;
+use datex_core::values::value_container::ValueContainer;
+use std::fmt::Display;
+use std::io::IsTerminal;
+use std::path::PathBuf;
+
+#[derive(Debug, Clone)]
+pub struct DiffOptions {
+    /// paths to the `.dx`, `.dxb` or `.json` files to compare
+    pub old: PathBuf,
+    pub new: PathBuf,
+}
+
+#[derive(Debug)]
+pub enum DiffError {
+    LoadError(LoadError),
+    /// number of differences between the values
+    Differences(usize),
+}
+
+impl CommandError for DiffError {
+    fn exit_code(&self) -> i32 {
+        match self {
+            DiffError::LoadError(e) => e.exit_code(),
+            DiffError::Differences(_) => EXIT_CODE_ERROR,
+        }
+    }
+}
+
+impl Display for DiffError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DiffError::LoadError(e) => write!(f, "{e}"),
+            DiffError::Differences(count) => write!(f, "{count} difference(s) found"),
+        }
+    }
+}
+
+impl From<LoadError> for DiffError {
+    fn from(err: LoadError) -> Self {
+        DiffError::LoadError(err)
+    }
+}
+
+/// A single difference between two values at a path
+enum Change {
+    Added(String, ValueContainer),
+    Removed(String, ValueContainer),
+    Changed(String, ValueContainer, ValueContainer),
+}
+
+impl Change {
+    /// Returns the change as a line prefixed with `+`, `-` or `~`,
+    /// colored green, red or yellow if colorized
+    fn render(&self, colorized: bool) -> String {
+        // the root value has an empty path
+        let root = |path: &str| {
+            if path.is_empty() {
+                ".".to_string()
+            } else {
+                path.to_string()
+            }
+        };
+        let (color, line) = match self {
+            Change::Added(path, value) => {
+                ("32", format!("+ {}: {}", root(path), compact_source(value)))
+            }
+            Change::Removed(path, value) => {
+                ("31", format!("- {}: {}", root(path), compact_source(value)))
+            }
+            Change::Changed(path, old, new) => (
+                "33",
+                format!(
+                    "~ {}: {} -> {}",
+                    root(path),
+                    compact_source(old),
+                    compact_source(new)
+                ),
+            ),
+        };
+        if colorized {
+            format!("\x1b[{color}m{line}\x1b[0m")
+        } else {
+            line
+        }
+    }
+}
+
+/// Appends a map key to a path, using `.name` for identifiers
+/// and `[key]` for all other keys
+fn key_path(path: &str, key: &ValueContainer) -> String {
+    if let CoreValue::Text(text) = &key.to_value().borrow().inner {
+        let is_identifier = text
+            .0
+            .chars()
+            .next()
+            .is_some_and(|c| c.is_alphabetic() || c == '_')
+            && text.0.chars().all(|c| c.is_alphanumeric() || c == '_');
+        if is_identifier {
+            return format!("{path}.{}", text.0);
+        }
+    }
+    format!("{path}[{}]", compact_source(key))
+}
+
+/// Compares two values recursively and collects all changes.
+/// Maps are compared by key and lists by index, all other values are compared as a whole.
+fn diff_values(path: &str, old: &ValueContainer, new: &ValueContainer, changes: &mut Vec<Change>) {
+    let old_value = old.to_value().borrow().inner.clone();
+    let new_value = new.to_value().borrow().inner.clone();
+    match (old_value, new_value) {
+        (CoreValue::Map(old_map), CoreValue::Map(new_map)) => {
+            for (key, old_entry) in old_map.clone() {
+                let key = ValueContainer::from(key);
+                let entry_path = key_path(path, &key);
+                match new_map.get(&key) {
+                    Some(new_entry) => diff_values(&entry_path, &old_entry, new_entry, changes),
+                    None => changes.push(Change::Removed(entry_path, old_entry)),
+                }
+            }
+            for (key, new_entry) in new_map {
+                let key = ValueContainer::from(key);
+                if old_map.get(&key).is_none() {
+                    changes.push(Change::Added(key_path(path, &key), new_entry));
+                }
+            }
+        }
+        (CoreValue::List(old_list), CoreValue::List(new_list)) => {
+            let old_items = old_list.as_vec();
+            let new_items = new_list.as_vec();
+            for (index, old_item) in old_items.iter().enumerate() {
+                let item_path = format!("{path}[{index}]");
+                match new_items.get(index) {
+                    Some(new_item) => diff_values(&item_path, old_item, new_item, changes),
+                    None => changes.push(Change::Removed(item_path, old_item.clone())),
+                }
+            }
+            for (index, new_item) in new_items.iter().enumerate().skip(old_items.len()) {
+                changes.push(Change::Added(format!("{path}[{index}]"), new_item.clone()));
+            }
+        }
+        _ if old == new => {}
+        _ => changes.push(Change::Changed(path.to_string(), old.clone(), new.clone())),
+    }
+}
+
+/// Prints a structural diff between the static values of two files.
+/// Returns an error with the number of differences if the values are not equal.
+pub fn diff(options: DiffOptions) -> Result<(), DiffError> {
+    let old = load_static_value(&options.old)?;
+    let new = load_static_value(&options.new)?;
+
+    let mut changes = vec![];
+    diff_values("", &old, &new, &mut changes);
+
+    let colorized = std::io::stdout().is_terminal();
+    for change in &changes {
+        println!("{}", change.render(colorized));
+    }
+
+    if changes.is_empty() {
+        println!("No differences");
+        Ok(())
+    } else {
+        Err(DiffError::Differences(changes.len()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use datex_core::compiler::extract_static_value_from_script;
+
+    fn value(source: &str) -> ValueContainer {
+        extract_static_value_from_script(source).unwrap().unwrap()
+    }
+
+    /// Compares two static values and returns the uncolored changes
+    fn diff_lines(old: &str, new: &str) -> Vec<String> {
+        let mut changes = vec![];
+        diff_values("", &value(old), &value(new), &mut changes);
+        changes.iter().map(|change| change.render(false)).collect()
+    }
+
+    #[test]
+    fn equal_values_have_no_changes() {
+        assert!(diff_lines("{a: 1, b: [1, 2]}", "{b: [1, 2], a: 1}").is_empty());
+    }
+
+    #[test]
+    fn reports_added_removed_and_changed_keys() {
+        let mut lines = diff_lines("{a: 1, b: 2}", "{b: 3, c: 4}");
+        lines.sort();
+        assert_eq!(lines, vec!["+ .c: 4", "- .a: 1", "~ .b: 2 -> 3"]);
+    }
+
+    #[test]
+    fn reports_nested_paths() {
+        assert_eq!(
+            diff_lines("{a: {b: {c: 1}}}", "{a: {b: {c: \"x\"}}}"),
+            vec!["~ .a.b.c: 1 -> \"x\""]
+        );
+    }
+
+    #[test]
+    fn compares_lists_by_index() {
+        assert_eq!(
+            diff_lines("{items: [1, 2, 3]}", "{items: [1, 5]}"),
+            vec!["~ .items[1]: 2 -> 5", "- .items[2]: 3"]
+        );
+        assert_eq!(diff_lines("[1]", "[1, [2]]"), vec!["+ [1]: [2]"]);
+    }
+
+    #[test]
+    fn reports_root_values_as_dot() {
+        assert_eq!(diff_lines("1", "[1]"), vec!["~ .: 1 -> [1]"]);
+    }
+
+    #[test]
+    fn quotes_keys_that_are_not_identifiers() {
+        let text = |text: &str| ValueContainer::from(text.to_string());
+        assert_eq!(key_path("", &text("name")), ".name");
+        assert_eq!(key_path(".a", &text("_id2")), ".a._id2");
+        assert_eq!(key_path("", &text("first name")), "[\"first name\"]");
+        assert_eq!(key_path("", &text("2nd")), "[\"2nd\"]");
+        assert_eq!(key_path(".list", &value("1")), ".list[1]");
+    }
+}
@@ -0,0 +1,387 @@
+use crate::utils::diagnostics::{Diagnostic, compiler_error_diagnostics, format_compiler_errors};
+use crate::utils::dxb::{DecompileError, read_dxb_body};
+use crate::utils::errors::{CommandError, EXIT_CODE_COMPILER_ERROR, EXIT_CODE_ERROR};
+use binrw::BinRead;
+use datex_core::ast::assignment_operation::AssignmentOperator;
+use datex_core::compiler::compile_script;
+use datex_core::global::instruction_codes::InstructionCode;
+use datex_core::global::protocol_structures::instructions::{
+    ApplyData, DecimalData, ExecutionBlockData, Float32Data, Float64Data, FloatAsInt16Data,
+    FloatAsInt32Data, Instruction, Int8Data, Int16Data, Int32Data, Int64Data, Int128Data,
+    IntegerData, RawFullPointerAddress, RawInternalPointerAddress, RawLocalPointerAddress,
+    ShortTextData, ShortTextDataRaw, SlotAddress, TextData, TextDataRaw, TypeInstruction,
+    UInt8Data, UInt16Data, UInt32Data, UInt64Data, UInt128Data,
+};
+use datex_core::global::type_instruction_codes::TypeSpaceInstructionCode;
+use datex_core::values::core_values::endpoint::Endpoint;
+use std::fmt::Display;
+use std::fs;
+use std::io::Cursor;
+use std::path::PathBuf;
+
+/// Maximum number of raw bytes shown per instruction in the listing
+const MAX_DISPLAYED_BYTES: usize = 8;
+
+#[derive(Debug, Clone)]
+pub struct DisasmOptions {
+    /// path to a DATEX script (`.dx`) or DXB file, or `-` to read a hex string from stdin
+    pub file: PathBuf,
+}
+
+#[derive(Debug)]
+pub enum DisasmError {
+    IOError(std::io::Error),
+    CompilerError(Vec<Diagnostic>),
+    DecompileError(DecompileError),
+    InvalidInstruction { offset: usize, message: String },
+}
+
+impl CommandError for DisasmError {
+    fn exit_code(&self) -> i32 {
+        match self {
+            DisasmError::CompilerError(_) => EXIT_CODE_COMPILER_ERROR,
+            _ => EXIT_CODE_ERROR,
+        }
+    }
+}
+
+impl Display for DisasmError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DisasmError::IOError(e) => write!(f, "[IO Error] {e}"),
+            DisasmError::CompilerError(diagnostics) => {
+                write!(f, "{}", format_compiler_errors(diagnostics))
+            }
+            DisasmError::DecompileError(e) => write!(f, "{e}"),
+            DisasmError::InvalidInstruction { offset, message } => {
+                write!(f, "[Invalid Instruction] at offset {offset:04x}: {message}")
+            }
+        }
+    }
+}
+
+impl From<std::io::Error> for DisasmError {
+    fn from(err: std::io::Error) -> Self {
+        DisasmError::IOError(err)
+    }
+}
+impl From<DecompileError> for DisasmError {
+    fn from(err: DecompileError) -> Self {
+        DisasmError::DecompileError(err)
+    }
+}
+
+/// A decoded instruction with its position in the DXB body
+pub struct DisassembledInstruction {
+    pub offset: usize,
+    pub bytes: Vec<u8>,
+    pub instruction: Instruction,
+}
+
+impl Display for DisassembledInstruction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut bytes = self
+            .bytes
+            .iter()
+            .take(MAX_DISPLAYED_BYTES)
+            .map(|b| format!("{b:02x}"))
+            .collect::<Vec<_>>()
+            .join(" ");
+        if self.bytes.len() > MAX_DISPLAYED_BYTES {
+            bytes.push_str(" ..");
+        }
+        write!(f, "{:04x}  {bytes:<26}  {}", self.offset, self.instruction)
+    }
+}
+
+/// Reads a binary operand from the DXB body
+fn read_operand<T>(reader: &mut Cursor<&[u8]>) -> Result<T, String>
+where
+    T: BinRead,
+    for<'a> T::Args<'a>: Default,
+{
+    T::read_le(reader).map_err(|e| e.to_string())
+}
+
+/// Reads the type space instructions following a type instruction code
+fn read_type_instructions(reader: &mut Cursor<&[u8]>) -> Result<Vec<TypeInstruction>, String> {
+    let mut instructions = vec![];
+    while (reader.position() as usize) < reader.get_ref().len() {
+        let code = read_operand::<u8>(reader)?;
+        let code = TypeSpaceInstructionCode::try_from(code)
+            .map_err(|_| format!("invalid type instruction code {code:02x}"))?;
+        instructions.push(match code {
+            TypeSpaceInstructionCode::TYPE_LIST_START => TypeInstruction::ListStart,
+            TypeSpaceInstructionCode::TYPE_LITERAL_INTEGER => {
+                TypeInstruction::LiteralInteger(read_operand::<IntegerData>(reader)?)
+            }
+            code => return Err(format!("unsupported type instruction {code:?}")),
+        });
+    }
+    Ok(instructions)
+}
+
+/// Decodes the next instruction from the DXB body.
+/// Mirrors `datex_core::parser::body::iterate_instructions`, which is not public in
+/// datex-core 0.0.6 and does not expose the offsets of the instructions. Replace this with
+/// the core parser once it is public. Codes that are not known here are reported as
+/// unsupported, and the round-trip tests fail if the compiler emits them.
+fn read_instruction(reader: &mut Cursor<&[u8]>) -> Result<Instruction, String> {
+    let code = read_operand::<u8>(reader)?;
+    let code = InstructionCode::try_from(code)
+        .map_err(|_| format!("invalid instruction code {code:02x}"))?;
+
+    Ok(match code {
+        InstructionCode::INT_8 => Instruction::Int8(read_operand::<Int8Data>(reader)?),
+        InstructionCode::INT_16 => Instruction::Int16(read_operand::<Int16Data>(reader)?),
+        InstructionCode::INT_32 => Instruction::Int32(read_operand::<Int32Data>(reader)?),
+        InstructionCode::INT_64 => Instruction::Int64(read_operand::<Int64Data>(reader)?),
+        InstructionCode::INT_128 => Instruction::Int128(read_operand::<Int128Data>(reader)?),
+        InstructionCode::UINT_8 => Instruction::UInt8(read_operand::<UInt8Data>(reader)?),
+        InstructionCode::UINT_16 => Instruction::UInt16(read_operand::<UInt16Data>(reader)?),
+        InstructionCode::UINT_32 => Instruction::UInt32(read_operand::<UInt32Data>(reader)?),
+        InstructionCode::UINT_64 => Instruction::UInt64(read_operand::<UInt64Data>(reader)?),
+        InstructionCode::UINT_128 => Instruction::UInt128(read_operand::<UInt128Data>(reader)?),
+        InstructionCode::INT_BIG => Instruction::BigInteger(read_operand::<IntegerData>(reader)?),
+
+        InstructionCode::DECIMAL_F32 => {
+            Instruction::DecimalF32(read_operand::<Float32Data>(reader)?)
+        }
+        InstructionCode::DECIMAL_F64 => {
+            Instruction::DecimalF64(read_operand::<Float64Data>(reader)?)
+        }
+        InstructionCode::DECIMAL_BIG => Instruction::Decimal(read_operand::<DecimalData>(reader)?),
+        InstructionCode::DECIMAL_AS_INT_16 => {
+            Instruction::DecimalAsInt16(read_operand::<FloatAsInt16Data>(reader)?)
+        }
+        InstructionCode::DECIMAL_AS_INT_32 => {
+            Instruction::DecimalAsInt32(read_operand::<FloatAsInt32Data>(reader)?)
+        }
+
+        InstructionCode::REMOTE_EXECUTION => Instruction::RemoteExecution,
+        InstructionCode::EXECUTION_BLOCK => {
+            Instruction::ExecutionBlock(read_operand::<ExecutionBlockData>(reader)?)
+        }
+
+        InstructionCode::SHORT_TEXT => {
+            let raw = read_operand::<ShortTextDataRaw>(reader)?;
+            Instruction::ShortText(ShortTextData(
+                String::from_utf8(raw.text).map_err(|e| e.to_string())?,
+            ))
+        }
+        InstructionCode::TEXT => {
+            let raw = read_operand::<TextDataRaw>(reader)?;
+            Instruction::Text(TextData(
+                String::from_utf8(raw.text).map_err(|e| e.to_string())?,
+            ))
+        }
+        InstructionCode::ENDPOINT => Instruction::Endpoint(read_operand::<Endpoint>(reader)?),
+        InstructionCode::TRUE => Instruction::True,
+        InstructionCode::FALSE => Instruction::False,
+        InstructionCode::NULL => Instruction::Null,
+
+        // complex terms
+        InstructionCode::LIST_START => Instruction::ListStart,
+        InstructionCode::MAP_START => Instruction::MapStart,
+        InstructionCode::SCOPE_START => Instruction::ScopeStart,
+        InstructionCode::SCOPE_END => Instruction::ScopeEnd,
+        InstructionCode::KEY_VALUE_SHORT_TEXT => {
+            let raw = read_operand::<ShortTextDataRaw>(reader)?;
+            Instruction::KeyValueShortText(ShortTextData(
+                String::from_utf8(raw.text).map_err(|e| e.to_string())?,
+            ))
+        }
+        InstructionCode::KEY_VALUE_DYNAMIC => Instruction::KeyValueDynamic,
+        InstructionCode::CLOSE_AND_STORE => Instruction::CloseAndStore,
+
+        InstructionCode::APPLY_ZERO => Instruction::Apply(ApplyData { arg_count: 0 }),
+        InstructionCode::APPLY_SINGLE => Instruction::Apply(ApplyData { arg_count: 1 }),
+        InstructionCode::APPLY => Instruction::Apply(read_operand::<ApplyData>(reader)?),
+
+        InstructionCode::DEREF => Instruction::Deref,
+        InstructionCode::ASSIGN_TO_REF => {
+            let operator = read_operand::<u8>(reader)?;
+            let operator = InstructionCode::try_from(operator)
+                .ok()
+                .and_then(|code| AssignmentOperator::try_from(code).ok())
+                .ok_or_else(|| format!("invalid assignment operator {operator:02x}"))?;
+            Instruction::AssignToReference(operator)
+        }
+
+        // operations
+        InstructionCode::ADD => Instruction::Add,
+        InstructionCode::SUBTRACT => Instruction::Subtract,
+        InstructionCode::MULTIPLY => Instruction::Multiply,
+        InstructionCode::DIVIDE => Instruction::Divide,
+        InstructionCode::UNARY_MINUS => Instruction::UnaryMinus,
+        InstructionCode::UNARY_PLUS => Instruction::UnaryPlus,
+        InstructionCode::BITWISE_NOT => Instruction::BitwiseNot,
+
+        // equality
+        InstructionCode::STRUCTURAL_EQUAL => Instruction::StructuralEqual,
+        InstructionCode::EQUAL => Instruction::Equal,
+        InstructionCode::NOT_STRUCTURAL_EQUAL => Instruction::NotStructuralEqual,
+        InstructionCode::NOT_EQUAL => Instruction::NotEqual,
+        InstructionCode::IS => Instruction::Is,
+        InstructionCode::MATCHES => Instruction::Matches,
+
+        // references
+        InstructionCode::CREATE_REF => Instruction::CreateRef,
+        InstructionCode::CREATE_REF_MUT => Instruction::CreateRefMut,
+        InstructionCode::CREATE_REF_FINAL => Instruction::CreateRefFinal,
+        InstructionCode::GET_REF => {
+            Instruction::GetRef(read_operand::<RawFullPointerAddress>(reader)?)
+        }
+        InstructionCode::GET_LOCAL_REF => {
+            Instruction::GetLocalRef(read_operand::<RawLocalPointerAddress>(reader)?)
+        }
+        InstructionCode::GET_INTERNAL_REF => {
+            Instruction::GetInternalRef(read_operand::<RawInternalPointerAddress>(reader)?)
+        }
+
+        // slots
+        InstructionCode::ALLOCATE_SLOT => {
+            Instruction::AllocateSlot(read_operand::<SlotAddress>(reader)?)
+        }
+        InstructionCode::GET_SLOT => Instruction::GetSlot(read_operand::<SlotAddress>(reader)?),
+        InstructionCode::DROP_SLOT => Instruction::DropSlot(read_operand::<SlotAddress>(reader)?),
+        InstructionCode::SET_SLOT => Instruction::SetSlot(read_operand::<SlotAddress>(reader)?),
+        InstructionCode::ADD_ASSIGN => Instruction::AddAssign(read_operand::<SlotAddress>(reader)?),
+        InstructionCode::SUBTRACT_ASSIGN => {
+            Instruction::SubtractAssign(read_operand::<SlotAddress>(reader)?)
+        }
+
+        // types
+        InstructionCode::TYPED_VALUE => {
+            Instruction::TypeInstructions(read_type_instructions(reader)?)
+        }
+        InstructionCode::TYPE_EXPRESSION => {
+            Instruction::TypeExpression(read_type_instructions(reader)?)
+        }
+
+        code => return Err(format!("unsupported instruction {code}")),
+    })
+}
+
+/// Decodes all instructions of a DXB body, including their byte offsets
+pub fn disassemble(dxb_body: &[u8]) -> Result<Vec<DisassembledInstruction>, DisasmError> {
+    let mut reader = Cursor::new(dxb_body);
+    let mut instructions = vec![];
+
+    while (reader.position() as usize) < dxb_body.len() {
+        let offset = reader.position() as usize;
+        let instruction = read_instruction(&mut reader)
+            .map_err(|message| DisasmError::InvalidInstruction { offset, message })?;
+        instructions.push(DisassembledInstruction {
+            offset,
+            bytes: dxb_body[offset..reader.position() as usize].to_vec(),
+            instruction,
+        });
+    }
+
+    Ok(instructions)
+}
+
+/// Prints an annotated instruction listing for a DATEX script or DXB file.
+/// DATEX scripts are compiled first, DXB files are read directly.
+/// Instructions are listed without source spans, since the compiler does not
+/// record source positions for the emitted bytecode.
+pub fn disasm(options: DisasmOptions) -> Result<(), DisasmError> {
+    let is_script = options.file.extension().and_then(|ext| ext.to_str()) == Some("dx");
+    let dxb = if is_script {
+        let script = fs::read_to_string(&options.file)?;
+        compile_script(&script, Default::default())
+            .map_err(|e| {
+                DisasmError::CompilerError(compiler_error_diagnostics(&e, &options.file, &script))
+            })?
+            .0
+    } else {
+        read_dxb_body(&options.file)?
+    };
+
+    println!("; {} ({} bytes)", options.file.display(), dxb.len());
+    for instruction in disassemble(&dxb)? {
+        println!("{instruction}");
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use datex_core::decompiler::decompile_body;
+
+    fn compile(script: &str) -> Vec<u8> {
+        compile_script(script, Default::default()).unwrap().0
+    }
+
+    fn listing(dxb: &[u8]) -> Vec<String> {
+        disassemble(dxb)
+            .unwrap()
+            .iter()
+            .map(|instruction| instruction.instruction.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn disassembles_compiled_scripts_completely() {
+        let scripts = [
+            "1 + 2",
+            "[1, {a: \"text\", b: true}, null]",
+            "const x = 42; x",
+            "1.5",
+            "-1000000",
+            "@example :: 1",
+            "&mut [1, 2]",
+            &format!("\"{}\"", "a".repeat(300)),
+        ];
+        for script in scripts {
+            let dxb = compile(script);
+            // the core decompiler accepts the same bytecode
+            assert!(decompile_body(&dxb, Default::default()).is_ok(), "{script}");
+
+            let instructions = disassemble(&dxb).unwrap();
+            let mut offset = 0;
+            for instruction in &instructions {
+                assert_eq!(instruction.offset, offset, "{script}");
+                assert!(!instruction.bytes.is_empty(), "{script}");
+                offset += instruction.bytes.len();
+            }
+            let bytes = instructions
+                .iter()
+                .flat_map(|instruction| instruction.bytes.clone())
+                .collect::<Vec<_>>();
+            assert_eq!(bytes, dxb, "{script}");
+        }
+    }
+
+    #[test]
+    fn lists_instructions_with_operands() {
+        assert_eq!(listing(&compile("1 + 2")), ["ADD", "INT_8 1", "INT_8 2"]);
+        assert_eq!(
+            listing(&compile("const x = 42; x")),
+            [
+                "ALLOCATE_SLOT 0",
+                "INT_8 42",
+                "SCOPE_END",
+                "CLOSE_AND_STORE",
+                "GET_SLOT 0"
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_the_offset_of_invalid_instructions() {
+        let dxb = compile("1 + 2");
+        // the last operand is cut off
+        assert!(matches!(
+            disassemble(&dxb[..dxb.len() - 1]),
+            Err(DisasmError::InvalidInstruction { offset: 3, .. })
+        ));
+        assert!(matches!(
+            disassemble(&[0x5b, 0xfe]),
+            Err(DisasmError::InvalidInstruction { offset: 1, .. })
+        ));
+    }
+}
@@ -0,0 +1,208 @@
+use crate::utils::diagnostics::{compiler_error_diagnostics, format_compiler_errors};
+use crate::utils::errors::{CommandError, EXIT_CODE_ERROR};
+use crate::utils::files::collect_dx_files;
+use crate::utils::output::{canonical_source, print_error, print_warning};
+use datex_core::ast::lexer::Token;
+use datex_core::compiler::error::CompilerError;
+use datex_core::compiler::extract_static_value_from_script;
+use logos::Logos;
+use std::fmt::Display;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone)]
+pub struct FmtOptions {
+    /// files or directories to format
+    pub paths: Vec<PathBuf>,
+    /// only check if the files are formatted, without writing them
+    pub check: bool,
+}
+
+#[derive(Debug)]
+pub enum FmtError {
+    IOError(std::io::Error),
+    /// number of files that are not formatted (in check mode)
+    Unformatted(usize),
+    /// number of files that could not be formatted because of compiler errors
+    Failed(usize),
+}
+
+impl CommandError for FmtError {
+    fn exit_code(&self) -> i32 {
+        EXIT_CODE_ERROR
+    }
+}
+
+impl Display for FmtError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FmtError::IOError(e) => write!(f, "[IO Error] {e}"),
+            FmtError::Unformatted(count) => write!(f, "{count} file(s) are not formatted"),
+            FmtError::Failed(count) => write!(f, "{count} file(s) could not be formatted"),
+        }
+    }
+}
+
+impl From<std::io::Error> for FmtError {
+    fn from(err: std::io::Error) -> Self {
+        FmtError::IOError(err)
+    }
+}
+
+/// Returns true if the source contains comments, which are not part
+/// of the parsed value and would be lost when formatting.
+fn contains_comments(source: &str) -> bool {
+    // the lexer skips comments, so they show up as gaps between the token spans
+    let mut end = 0;
+    for (token, span) in Token::lexer(source).spanned() {
+        if span.start != end || matches!(token, Ok(Token::LineDoc(_))) {
+            return true;
+        }
+        end = span.end;
+    }
+    end != source.len()
+}
+
+/// Reason why a file could not be formatted
+enum FormatFailure {
+    /// the file is valid, but can not be formatted yet (e.g. it contains comments or
+    /// non-static values). Such files are skipped with a warning.
+    Unsupported(String),
+    /// the file contains compiler errors
+    CompilerError(String),
+}
+
+/// Splits a leading shebang line (e.g. `#!/usr/bin/env datex`) from the source
+fn split_shebang(source: &str) -> (&str, &str) {
+    if !source.starts_with("#!") {
+        return ("", source);
+    }
+    match source.find('\n') {
+        Some(pos) => source.split_at(pos + 1),
+        None => (source, ""),
+    }
+}
+
+/// Formats a single DATEX source file.
+/// Only scripts that consist of a single static value can be formatted,
+/// a shebang line is kept as it is.
+fn format_source(path: &Path, source: &str) -> Result<String, FormatFailure> {
+    let (shebang, body) = split_shebang(source);
+    if contains_comments(body) {
+        return Err(FormatFailure::Unsupported(
+            "files with comments can not be formatted yet".to_string(),
+        ));
+    }
+    // the parser skips the shebang line itself, so diagnostics have positions in the full source
+    match extract_static_value_from_script(source) {
+        Ok(Some(value)) => Ok(format!("{shebang}{}", canonical_source(&value))),
+        Ok(None) | Err(CompilerError::NonStaticValue) => Err(FormatFailure::Unsupported(
+            "only static values can be formatted".to_string(),
+        )),
+        Err(e) => Err(FormatFailure::CompilerError(format_compiler_errors(
+            &compiler_error_diagnostics(&e, path, source),
+        ))),
+    }
+}
+
+/// Formats all `.dx` files in the given paths in the canonical multiline style.
+/// In check mode, the files are not written and an error is returned if any file
+/// is not formatted. Files that can not be formatted yet are skipped with a warning.
+pub fn fmt(options: FmtOptions) -> Result<(), FmtError> {
+    let paths = if options.paths.is_empty() {
+        vec![PathBuf::from(".")]
+    } else {
+        options.paths
+    };
+
+    let mut unformatted = 0;
+    let mut failed = 0;
+
+    for path in collect_dx_files(&paths)? {
+        let source = fs::read_to_string(&path)?;
+        match format_source(&path, &source) {
+            Ok(formatted) if formatted == source => {}
+            Ok(formatted) => {
+                if options.check {
+                    println!("Not formatted: {}", path.display());
+                    unformatted += 1;
+                } else {
+                    fs::write(&path, formatted)?;
+                    println!("Formatted {}", path.display());
+                }
+            }
+            Err(FormatFailure::Unsupported(reason)) => {
+                print_warning(&format!("Skipped {}: {reason}", path.display()));
+            }
+            Err(FormatFailure::CompilerError(e)) => {
+                print_error(&e);
+                failed += 1;
+            }
+        }
+    }
+
+    if failed > 0 {
+        Err(FmtError::Failed(failed))
+    } else if unformatted > 0 {
+        Err(FmtError::Unformatted(unformatted))
+    } else {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn format(source: &str) -> Result<String, FormatFailure> {
+        format_source(Path::new("test.dx"), source)
+    }
+
+    #[test]
+    fn formats_static_values() {
+        assert_eq!(format("{a:1,b:[1,2]}").ok().unwrap(), "{a: 1, b: [1, 2]}\n");
+    }
+
+    #[test]
+    fn keeps_formatted_files() {
+        let formatted = format("{a:1}").ok().unwrap();
+        assert_eq!(format(&formatted).ok().unwrap(), formatted);
+    }
+
+    #[test]
+    fn keeps_shebang() {
+        let formatted = format("#!/usr/bin/env datex\n{a:1}").ok().unwrap();
+        assert!(formatted.starts_with("#!/usr/bin/env datex\n{"));
+        assert_eq!(format(&formatted).ok().unwrap(), formatted);
+    }
+
+    #[test]
+    fn skips_non_static_scripts() {
+        assert!(matches!(
+            format("const x = 1; x"),
+            Err(FormatFailure::Unsupported(_))
+        ));
+        assert!(matches!(
+            format("1 + 2"),
+            Err(FormatFailure::Unsupported(_))
+        ));
+    }
+
+    #[test]
+    fn skips_files_with_comments() {
+        assert!(matches!(
+            format("// config\n{a: 1}"),
+            Err(FormatFailure::Unsupported(_))
+        ));
+    }
+
+    #[test]
+    fn reports_compiler_errors() {
+        match format("{a: 1") {
+            Err(FormatFailure::CompilerError(e)) => {
+                assert!(e.starts_with("[Compiler Error] test.dx:"))
+            }
+            _ => panic!("expected a compiler error"),
+        }
+    }
+}
@@ -0,0 +1,205 @@
+use crate::utils::errors::{CommandError, EXIT_CODE_ERROR};
+use clap::ValueEnum;
+use datex_core::decompiler::apply_syntax_highlighting;
+use std::fmt::Display;
+use std::fs;
+use std::path::PathBuf;
+
+/// Output format of `datex highlight`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum HighlightFormat {
+    /// 24-bit ANSI escape sequences, as in the REPL
+    #[default]
+    Ansi,
+    /// HTML `<pre>` element with inline colored spans
+    Html,
+}
+
+#[derive(Debug, Clone)]
+pub struct HighlightOptions {
+    /// path to the DATEX script, or `-` to read from stdin
+    pub file: PathBuf,
+    pub format: HighlightFormat,
+}
+
+#[derive(Debug)]
+pub enum HighlightError {
+    IOError(std::io::Error),
+    HighlightError(String),
+}
+
+impl CommandError for HighlightError {
+    fn exit_code(&self) -> i32 {
+        EXIT_CODE_ERROR
+    }
+}
+
+impl Display for HighlightError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HighlightError::IOError(e) => write!(f, "[IO Error] {e}"),
+            HighlightError::HighlightError(e) => write!(f, "[Highlight Error] {e}"),
+        }
+    }
+}
+
+impl From<std::io::Error> for HighlightError {
+    fn from(err: std::io::Error) -> Self {
+        HighlightError::IOError(err)
+    }
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Converts the output of `apply_syntax_highlighting` into HTML.
+/// The highlighter only emits 24-bit foreground colors (`ESC[38;2;r;g;bm`)
+/// and resets (`ESC[0m`), which are mapped to colored spans.
+/// Adjacent tokens with the same color share a single span.
+fn ansi_to_html(ansi: &str) -> String {
+    let mut html = String::from("<pre class=\"datex\">");
+    let mut color: Option<String> = None;
+    let mut rest = ansi;
+    while let Some(start) = rest.find("\x1b[") {
+        html.push_str(&escape_html(&rest[..start]));
+        let sequence = &rest[start + 2..];
+        let Some(end) = sequence.find('m') else {
+            rest = "";
+            break;
+        };
+        let params = sequence[..end]
+            .split(';')
+            .map(|p| p.parse::<u8>().unwrap_or(0))
+            .collect::<Vec<_>>();
+        let next_color = match params[..] {
+            [38, 2, r, g, b] => Some(format!("#{r:02x}{g:02x}{b:02x}")),
+            _ => None,
+        };
+        if next_color != color {
+            if color.is_some() {
+                html.push_str("</span>");
+            }
+            if let Some(next_color) = &next_color {
+                html.push_str(&format!("<span style=\"color:{next_color}\">"));
+            }
+            color = next_color;
+        }
+        rest = &sequence[end + 1..];
+    }
+    html.push_str(&escape_html(rest));
+    if color.is_some() {
+        html.push_str("</span>");
+    }
+    html.push_str("</pre>");
+    html
+}
+
+/// Prints a syntax highlighted DATEX script as ANSI or HTML,
+/// using the same highlighter as the REPL.
+pub fn highlight(options: HighlightOptions) -> Result<(), HighlightError> {
+    let script = if options.file.as_os_str() == "-" {
+        std::io::read_to_string(std::io::stdin())?
+    } else {
+        fs::read_to_string(&options.file)?
+    };
+
+    let highlighted = apply_syntax_highlighting(script)
+        .map_err(|e| HighlightError::HighlightError(e.to_string()))?;
+
+    match options.format {
+        HighlightFormat::Ansi => print!("{highlighted}"),
+        HighlightFormat::Html => println!("{}", ansi_to_html(&highlighted)),
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RED: &str = "\x1b[38;2;255;0;0m";
+    const BLUE: &str = "\x1b[38;2;0;0;255m";
+    const RESET: &str = "\x1b[0m";
+
+    fn pre(inner: &str) -> String {
+        format!("<pre class=\"datex\">{inner}</pre>")
+    }
+
+    #[test]
+    fn escapes_html_characters() {
+        assert_eq!(
+            ansi_to_html("a < b && \"c\" > d"),
+            pre("a &lt; b &amp;&amp; &quot;c&quot; &gt; d")
+        );
+        assert_eq!(
+            ansi_to_html(&format!("{RED}\"<&>\"{RESET}")),
+            pre("<span style=\"color:#ff0000\">&quot;&lt;&amp;&gt;&quot;</span>")
+        );
+    }
+
+    #[test]
+    fn closes_spans_on_reset() {
+        assert_eq!(
+            ansi_to_html(&format!("{RED}1{RESET} + {BLUE}2{RESET}")),
+            pre("<span style=\"color:#ff0000\">1</span> + <span style=\"color:#0000ff\">2</span>")
+        );
+    }
+
+    #[test]
+    fn closes_unterminated_spans() {
+        assert_eq!(
+            ansi_to_html(&format!("{RED}x")),
+            pre("<span style=\"color:#ff0000\">x</span>")
+        );
+    }
+
+    #[test]
+    fn never_nests_spans() {
+        // a new color without a reset closes the previous span
+        assert_eq!(
+            ansi_to_html(&format!("{RED}a{BLUE}b{RESET}")),
+            pre("<span style=\"color:#ff0000\">a</span><span style=\"color:#0000ff\">b</span>")
+        );
+    }
+
+    #[test]
+    fn merges_adjacent_tokens_with_the_same_color() {
+        // the highlighter emits a color before every token without resets in between
+        assert_eq!(
+            ansi_to_html(&format!("{RED}a{RED} {RED}b{BLUE}c{RESET}")),
+            pre("<span style=\"color:#ff0000\">a b</span><span style=\"color:#0000ff\">c</span>")
+        );
+    }
+
+    #[test]
+    fn treats_other_sequences_as_reset() {
+        assert_eq!(
+            ansi_to_html(&format!("{RED}a\x1b[1mb")),
+            pre("<span style=\"color:#ff0000\">a</span>b")
+        );
+        // a truncated sequence drops the rest of the input
+        assert_eq!(ansi_to_html("a\x1b[38;2"), pre("a"));
+    }
+
+    #[test]
+    fn keeps_the_text_of_highlighted_scripts() {
+        let script = "const x = {a: \"<b>\"} & 1;";
+        let html = ansi_to_html(&apply_syntax_highlighting(script.to_string()).unwrap());
+        let mut text = String::new();
+        let mut in_tag = false;
+        for c in html.chars() {
+            match c {
+                '<' => in_tag = true,
+                '>' => in_tag = false,
+                c if !in_tag => text.push(c),
+                _ => {}
+            }
+        }
+        assert_eq!(text, escape_html(script));
+    }
+}
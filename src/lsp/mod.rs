@@ -30,7 +30,7 @@ impl LanguageServer for Backend {
         Ok(())
     }
 
-    async fn hover(&self, params: HoverParams) -> Result<Option<Hover>> {
+    async fn hover(&self, _params: HoverParams) -> Result<Option<Hover>> {
         self.client
             .log_message(MessageType::INFO, "server initialized!")
             .await;
@@ -45,8 +45,8 @@ impl LanguageServer for Backend {
     }
 }
 
-#[tokio::main]
-async fn main() {
+/// Serves the language server on stdin and stdout
+pub async fn serve() {
     let stdin = tokio::io::stdin();
     let stdout = tokio::io::stdout();
 
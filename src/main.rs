@@ -1,23 +1,43 @@
 use datex_core::crypto::crypto_native::CryptoNative;
 use datex_core::run_async;
 use datex_core::runtime::global_context::{DebugFlags, GlobalContext, set_global_context};
-use datex_core::runtime::{Runtime, RuntimeConfig};
 use datex_core::utils::time_native::TimeNative;
 use std::path::PathBuf;
 use std::sync::Arc;
 
+mod check;
 mod command_line_args;
+mod compile;
+mod convert;
+mod decompile;
+mod diff;
+mod disasm;
+mod fmt;
+mod highlight;
 mod lsp;
+mod query;
 mod repl;
+mod run;
 mod utils;
 mod workbench;
 
-use crate::command_line_args::Repl;
-use crate::lsp::Backend;
+use crate::check::{CheckOptions, check};
+use crate::command_line_args::{
+    Check, Compile, Convert, Decompile, Diff, Disasm, Fmt, Highlight, Query, Repl, Run,
+};
+use crate::compile::{CompileOptions, compile};
+use crate::convert::{ConvertOptions, convert};
+use crate::decompile::{DecompileOptions, decompile};
+use crate::diff::{DiffOptions, diff};
+use crate::disasm::{DisasmOptions, disasm};
+use crate::fmt::{FmtOptions, fmt};
+use crate::highlight::{HighlightOptions, highlight};
+use crate::query::{QueryOptions, query};
 use crate::repl::{ReplOptions, repl};
+use crate::run::{RunOptions, ScriptSource, run};
 use crate::utils::config::{ConfigError, create_runtime_with_config};
+use crate::utils::errors::exit_on_error;
 use command_line_args::{Subcommands, get_command};
-use tower_lsp::{LspService, Server};
 
 #[tokio::main]
 async fn main() {
@@ -25,26 +45,109 @@ async fn main() {
 
     if let Some(cmd) = command {
         match cmd {
-            Subcommands::Lsp(lsp) => {
-                // println!("Running LSP");
-                let stdin = tokio::io::stdin();
-                let stdout = tokio::io::stdout();
-
-                let (service, socket) = LspService::new(|client| Backend { client });
-                Server::new(stdin, stdout, socket).serve(service).await;
+            Subcommands::Lsp(_) => {
+                lsp::serve().await;
             }
-            Subcommands::Run(run) => {
-                if run.file.is_some() {
-                    println!("File: {}", run.file.unwrap())
-                }
-                let runtime = Runtime::new(RuntimeConfig::default());
+            Subcommands::Run(Run {
+                file,
+                eval,
+                named_args,
+                watch,
+                args,
+                verbose,
+                config,
+                output,
+            }) => {
+                let source = match (eval, file) {
+                    (Some(expression), _) => ScriptSource::Expression(expression),
+                    (None, Some(file)) if file.as_os_str() == "-" => ScriptSource::Stdin,
+                    (None, Some(file)) => ScriptSource::File(file),
+                    (None, None) => unreachable!("clap requires either a file or an expression"),
+                };
+                let options = RunOptions {
+                    source,
+                    args,
+                    named_args,
+                    watch,
+                    output_format: output,
+                    verbose,
+                    config_path: config,
+                };
+                exit_on_error(run(options).await);
+            }
+            Subcommands::Check(Check { paths, format }) => {
+                let options = CheckOptions { paths, format };
+                exit_on_error(check(options));
+            }
+            Subcommands::Compile(Compile { file, output }) => {
+                let options = CompileOptions { file, output };
+                exit_on_error(compile(options));
+            }
+            Subcommands::Convert(Convert {
+                file,
+                from,
+                to,
+                output,
+            }) => {
+                let options = ConvertOptions {
+                    file,
+                    from,
+                    to,
+                    output,
+                };
+                exit_on_error(convert(options));
+            }
+            Subcommands::Decompile(Decompile {
+                file,
+                compact,
+                plain,
+            }) => {
+                let options = DecompileOptions {
+                    file,
+                    compact,
+                    plain,
+                };
+                exit_on_error(decompile(options));
+            }
+            Subcommands::Diff(Diff { old, new }) => {
+                let options = DiffOptions { old, new };
+                exit_on_error(diff(options));
+            }
+            Subcommands::Disasm(Disasm { file }) => {
+                let options = DisasmOptions { file };
+                exit_on_error(disasm(options));
+            }
+            Subcommands::Fmt(Fmt { paths, check }) => {
+                let options = FmtOptions { paths, check };
+                exit_on_error(fmt(options));
+            }
+            Subcommands::Highlight(Highlight { file, format }) => {
+                let options = HighlightOptions { file, format };
+                exit_on_error(highlight(options));
+            }
+            Subcommands::Query(Query {
+                file,
+                query: query_string,
+                output,
+            }) => {
+                let options = QueryOptions {
+                    file,
+                    query: query_string,
+                    output_format: output,
+                };
+                exit_on_error(query(options));
             }
-            Subcommands::Repl(Repl { verbose, config }) => {
+            Subcommands::Repl(Repl {
+                verbose,
+                config,
+                output,
+            }) => {
                 let options = ReplOptions {
                     verbose,
                     config_path: config,
+                    output_format: output,
                 };
-                repl(options).await.unwrap();
+                exit_on_error(repl(options).await);
             }
             Subcommands::Workbench(_) => {
                 workbench(None, false).await.expect("Workbench failed");
@@ -53,7 +156,7 @@ async fn main() {
     }
     // run REPL if no command is provided
     else {
-        repl(ReplOptions::default()).await.unwrap();
+        exit_on_error(repl(ReplOptions::default()).await);
     }
 }
 
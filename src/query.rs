@@ -0,0 +1,540 @@
+use crate::utils::errors::{CommandError, EXIT_CODE_ERROR};
+use crate::utils::output::{OutputError, OutputFormat, compact_source, format_value};
+use crate::utils::static_value::{LoadError, load_static_value};
+use datex_core::compiler::extract_static_value_from_script;
+use datex_core::traits::structural_eq::StructuralEq;
+use datex_core::values::core_value::CoreValue;
+use datex_core::values::core_values::integer::Integer;
+use datex_core::values::core_values::list::List;
+use datex_core::values::value::Value;
+use datex_core::values::value_container::ValueContainer;
+use std::cmp::Ordering;
+use std::fmt::Display;
+use std::path::PathBuf;
+
+#[derive(Debug, Clone)]
+pub struct QueryOptions {
+    /// path to a `.dx`, `.dxb` or `.json` file, or `-` to read DATEX or JSON from stdin
+    pub file: PathBuf,
+    /// jq-style query, e.g. `.users[] | select(.age > 30) | .name`
+    pub query: String,
+    pub output_format: OutputFormat,
+}
+
+#[derive(Debug)]
+pub enum QueryError {
+    LoadError(LoadError),
+    InvalidQuery(String),
+    EvaluationError(String),
+    OutputError(OutputError),
+}
+
+impl CommandError for QueryError {
+    fn exit_code(&self) -> i32 {
+        match self {
+            QueryError::LoadError(e) => e.exit_code(),
+            _ => EXIT_CODE_ERROR,
+        }
+    }
+}
+
+impl Display for QueryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            QueryError::LoadError(e) => write!(f, "{e}"),
+            QueryError::InvalidQuery(e) => write!(f, "[Invalid Query] {e}"),
+            QueryError::EvaluationError(e) => write!(f, "[Query Error] {e}"),
+            QueryError::OutputError(e) => write!(f, "[Output Error] {e}"),
+        }
+    }
+}
+
+impl From<LoadError> for QueryError {
+    fn from(err: LoadError) -> Self {
+        QueryError::LoadError(err)
+    }
+}
+impl From<OutputError> for QueryError {
+    fn from(err: OutputError) -> Self {
+        QueryError::OutputError(err)
+    }
+}
+
+/// A single step in a path, e.g. `.name`, `[0]` or `[]`
+#[derive(Debug, Clone)]
+enum Segment {
+    /// map entry or list item
+    Key(ValueContainer),
+    /// all list items or map values
+    Iterate,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Comparison {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+/// A filter in a query pipeline, separated by `|`
+#[derive(Debug, Clone)]
+enum Filter {
+    Path(Vec<Segment>),
+    /// `select(path)` or `select(path <op> value)`
+    Select(Vec<Segment>, Option<(Comparison, ValueContainer)>),
+    Keys,
+    Length,
+}
+
+/// Returns the byte index of the first character at the top level of the query
+/// (outside of strings, brackets and parentheses) that matches the predicate
+fn find_top_level(query: &str, predicate: impl Fn(&str) -> bool) -> Option<usize> {
+    let mut depth = 0;
+    let mut in_string = false;
+    let mut escaped = false;
+    for (i, c) in query.char_indices() {
+        if in_string {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+        match c {
+            '"' => in_string = true,
+            _ if depth == 0 && predicate(&query[i..]) => return Some(i),
+            '[' | '(' | '{' => depth += 1,
+            ']' | ')' | '}' => depth -= 1,
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Parses a DATEX literal used as key or comparison value in a query
+fn parse_literal(literal: &str) -> Result<ValueContainer, QueryError> {
+    match extract_static_value_from_script(literal) {
+        Ok(Some(value)) => Ok(value),
+        _ => Err(QueryError::InvalidQuery(format!(
+            "'{literal}' is not a static DATEX value"
+        ))),
+    }
+}
+
+/// Parses a path such as `.`, `.users[0].name`, `."first name"` or `.items[]`
+fn parse_path(path: &str) -> Result<Vec<Segment>, QueryError> {
+    if !path.starts_with('.') {
+        return Err(QueryError::InvalidQuery(format!(
+            "'{path}' is not a valid path, paths start with '.'"
+        )));
+    }
+    let mut segments = vec![];
+    let mut rest = path;
+    while !rest.is_empty() {
+        if let Some(after_bracket) = rest.strip_prefix('[') {
+            let end = find_top_level(after_bracket, |s| s.starts_with(']'))
+                .ok_or_else(|| QueryError::InvalidQuery(format!("missing ']' in '{path}'")))?;
+            let key = after_bracket[..end].trim();
+            segments.push(if key.is_empty() {
+                Segment::Iterate
+            } else {
+                Segment::Key(parse_literal(key)?)
+            });
+            rest = &after_bracket[end + 1..];
+        } else if let Some(after_dot) = rest.strip_prefix('.') {
+            if after_dot.starts_with('"') {
+                // the first top level character after the quoted key ends it
+                let end = find_top_level(after_dot, |_| true).unwrap_or(after_dot.len());
+                segments.push(Segment::Key(parse_literal(&after_dot[..end])?));
+                rest = &after_dot[end..];
+            } else {
+                let end = after_dot
+                    .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+                    .unwrap_or(after_dot.len());
+                if end > 0 {
+                    let name = &after_dot[..end];
+                    segments.push(Segment::Key(ValueContainer::from(name.to_string())));
+                } else if !(after_dot.is_empty() || after_dot.starts_with('[')) {
+                    return Err(QueryError::InvalidQuery(format!(
+                        "expected a field name after '.' in '{path}'"
+                    )));
+                }
+                rest = &after_dot[end..];
+            }
+        } else {
+            return Err(QueryError::InvalidQuery(format!(
+                "unexpected '{rest}' in '{path}'"
+            )));
+        }
+    }
+    Ok(segments)
+}
+
+/// Parses the condition of a `select(...)` filter
+fn parse_select(condition: &str) -> Result<Filter, QueryError> {
+    const OPERATORS: [(&str, Comparison); 6] = [
+        ("==", Comparison::Eq),
+        ("!=", Comparison::Ne),
+        ("<=", Comparison::Le),
+        (">=", Comparison::Ge),
+        ("<", Comparison::Lt),
+        (">", Comparison::Gt),
+    ];
+    let operator = find_top_level(condition, |s| {
+        OPERATORS.iter().any(|(op, _)| s.starts_with(op))
+    });
+    match operator {
+        Some(index) => {
+            let (symbol, comparison) = OPERATORS
+                .iter()
+                .find(|(op, _)| condition[index..].starts_with(op))
+                .unwrap();
+            let path = parse_path(condition[..index].trim())?;
+            let value = parse_literal(condition[index + symbol.len()..].trim())?;
+            Ok(Filter::Select(path, Some((*comparison, value))))
+        }
+        None => Ok(Filter::Select(parse_path(condition.trim())?, None)),
+    }
+}
+
+/// Parses a query pipeline such as `.users[] | select(.age > 30) | .name`
+fn parse_query(query: &str) -> Result<Vec<Filter>, QueryError> {
+    let mut filters = vec![];
+    let mut rest = query;
+    loop {
+        let end = find_top_level(rest, |s| s.starts_with('|')).unwrap_or(rest.len());
+        let filter = rest[..end].trim();
+        filters.push(match filter {
+            "keys" => Filter::Keys,
+            "length" => Filter::Length,
+            _ => match filter
+                .strip_prefix("select(")
+                .and_then(|s| s.strip_suffix(')'))
+            {
+                Some(condition) => parse_select(condition)?,
+                None => Filter::Path(parse_path(filter)?),
+            },
+        });
+        if end == rest.len() {
+            break;
+        }
+        rest = &rest[end + 1..];
+    }
+    Ok(filters)
+}
+
+fn null() -> ValueContainer {
+    ValueContainer::from(Value::null())
+}
+
+fn as_integer(value: &CoreValue) -> Option<i128> {
+    match value {
+        CoreValue::Integer(i) => i.as_i128(),
+        CoreValue::TypedInteger(i) => i.as_i128(),
+        _ => None,
+    }
+}
+
+fn as_number(value: &CoreValue) -> Option<f64> {
+    match value {
+        CoreValue::Decimal(d) => Some(d.into_f64()),
+        CoreValue::TypedDecimal(d) => Some(d.as_f64()),
+        _ => as_integer(value).map(|i| i as f64),
+    }
+}
+
+/// Applies a path segment to a value and returns the resulting values.
+/// Missing map entries and list items result in null, as in jq.
+fn apply_segment(
+    value: &ValueContainer,
+    segment: &Segment,
+) -> Result<Vec<ValueContainer>, QueryError> {
+    let inner = value.to_value().borrow().inner.clone();
+    match (segment, &inner) {
+        (Segment::Key(key), CoreValue::Map(map)) => {
+            Ok(vec![map.get(key).cloned().unwrap_or_else(null)])
+        }
+        (Segment::Key(key), CoreValue::List(list)) => {
+            let index = as_integer(&key.to_value().borrow().inner).ok_or_else(|| {
+                QueryError::EvaluationError(format!(
+                    "can not index a list with {}",
+                    compact_source(key)
+                ))
+            })?;
+            let index = if index < 0 {
+                index + list.len() as i128
+            } else {
+                index
+            };
+            let item = u32::try_from(index).ok().and_then(|i| list.get(i));
+            Ok(vec![item.cloned().unwrap_or_else(null)])
+        }
+        (Segment::Key(_), CoreValue::Null) => Ok(vec![null()]),
+        (Segment::Iterate, CoreValue::List(list)) => Ok(list.iter().cloned().collect()),
+        (Segment::Iterate, CoreValue::Map(map)) => {
+            Ok(map.clone().into_iter().map(|(_, value)| value).collect())
+        }
+        (Segment::Key(key), _) => Err(QueryError::EvaluationError(format!(
+            "can not index {} with {}",
+            compact_source(value),
+            compact_source(key)
+        ))),
+        (Segment::Iterate, _) => Err(QueryError::EvaluationError(format!(
+            "can not iterate over {}",
+            compact_source(value)
+        ))),
+    }
+}
+
+fn apply_path(value: &ValueContainer, path: &[Segment]) -> Result<Vec<ValueContainer>, QueryError> {
+    let mut values = vec![value.clone()];
+    for segment in path {
+        let mut next = vec![];
+        for value in &values {
+            next.extend(apply_segment(value, segment)?);
+        }
+        values = next;
+    }
+    Ok(values)
+}
+
+/// Checks a `select(...)` condition for a single value
+fn matches_condition(
+    value: &ValueContainer,
+    condition: &Option<(Comparison, ValueContainer)>,
+) -> bool {
+    let value = value.to_value().borrow().inner.clone();
+    let Some((comparison, expected)) = condition else {
+        // values other than null and false are truthy
+        return !matches!(value, CoreValue::Null)
+            && !matches!(value, CoreValue::Boolean(ref b) if !b.0);
+    };
+    let expected = expected.to_value().borrow().inner.clone();
+    let ordering = match (&value, &expected) {
+        (CoreValue::Text(a), CoreValue::Text(b)) => Some(a.0.cmp(&b.0)),
+        _ => match (as_number(&value), as_number(&expected)) {
+            (Some(a), Some(b)) => a.partial_cmp(&b),
+            _ => None,
+        },
+    };
+    match comparison {
+        Comparison::Eq => ordering.map_or_else(|| value.structural_eq(&expected), |o| o.is_eq()),
+        Comparison::Ne => !ordering.map_or_else(|| value.structural_eq(&expected), |o| o.is_eq()),
+        Comparison::Lt => ordering == Some(Ordering::Less),
+        Comparison::Le => matches!(ordering, Some(Ordering::Less | Ordering::Equal)),
+        Comparison::Gt => ordering == Some(Ordering::Greater),
+        Comparison::Ge => matches!(ordering, Some(Ordering::Greater | Ordering::Equal)),
+    }
+}
+
+fn apply_filter(
+    value: &ValueContainer,
+    filter: &Filter,
+) -> Result<Vec<ValueContainer>, QueryError> {
+    match filter {
+        Filter::Path(path) => apply_path(value, path),
+        Filter::Select(path, condition) => {
+            let selected = apply_path(value, path)?
+                .iter()
+                .any(|v| matches_condition(v, condition));
+            Ok(if selected {
+                vec![value.clone()]
+            } else {
+                vec![]
+            })
+        }
+        Filter::Keys => match &value.to_value().borrow().inner {
+            CoreValue::Map(map) => Ok(vec![ValueContainer::from(Value::from(CoreValue::List(
+                List::new(
+                    map.clone()
+                        .into_iter()
+                        .map(|(key, _)| ValueContainer::from(key))
+                        .collect(),
+                ),
+            )))]),
+            CoreValue::List(list) => Ok(vec![ValueContainer::from(Value::from(CoreValue::List(
+                List::new(
+                    (0..list.len())
+                        .map(|i| {
+                            ValueContainer::from(Value::from(CoreValue::Integer(Integer::from(
+                                i as i64,
+                            ))))
+                        })
+                        .collect(),
+                ),
+            )))]),
+            _ => Err(QueryError::EvaluationError(format!(
+                "{} has no keys",
+                compact_source(value)
+            ))),
+        },
+        Filter::Length => {
+            let length = match &value.to_value().borrow().inner {
+                CoreValue::Map(map) => map.size(),
+                CoreValue::List(list) => list.len() as usize,
+                CoreValue::Text(text) => text.0.chars().count(),
+                CoreValue::Null => 0,
+                _ => {
+                    return Err(QueryError::EvaluationError(format!(
+                        "{} has no length",
+                        compact_source(value)
+                    )));
+                }
+            };
+            Ok(vec![ValueContainer::from(Value::from(CoreValue::Integer(
+                Integer::from(length as i64),
+            )))])
+        }
+    }
+}
+
+/// Applies the filters of a query pipeline to a value and returns all results
+fn apply_filters(
+    value: ValueContainer,
+    filters: &[Filter],
+) -> Result<Vec<ValueContainer>, QueryError> {
+    let mut values = vec![value];
+    for filter in filters {
+        let mut next = vec![];
+        for value in &values {
+            next.extend(apply_filter(value, filter)?);
+        }
+        values = next;
+    }
+    Ok(values)
+}
+
+/// Runs a jq-style query on a static DATEX value and prints all results.
+pub fn query(options: QueryOptions) -> Result<(), QueryError> {
+    let filters = parse_query(&options.query)?;
+    let value = load_static_value(&options.file)?;
+
+    for value in &apply_filters(value, &filters)? {
+        println!("{}", format_value(value, options.output_format)?);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DATA: &str = r#"{
+        users: [
+            {name: "Alice", age: 42, "first name": "A"},
+            {name: "Bob", age: 27}
+        ],
+        nested: {a: {b: [1, 2, 3]}},
+        "with space": true
+    }"#;
+
+    /// Runs a query on the test data and returns the results as compact DATEX
+    fn run(query: &str) -> Result<Vec<String>, String> {
+        let value = parse_literal(DATA).unwrap();
+        let filters = parse_query(query).map_err(|e| e.to_string())?;
+        let values = apply_filters(value, &filters).map_err(|e| e.to_string())?;
+        Ok(values.iter().map(compact_source).collect())
+    }
+
+    #[test]
+    fn identity() {
+        assert_eq!(
+            run(".").unwrap(),
+            vec![compact_source(&parse_literal(DATA).unwrap())]
+        );
+    }
+
+    #[test]
+    fn nested_keys() {
+        assert_eq!(run(".nested.a.b").unwrap(), vec!["[1,2,3]"]);
+        assert_eq!(run(".users[1].name").unwrap(), vec!["\"Bob\""]);
+    }
+
+    #[test]
+    fn indices() {
+        assert_eq!(run(".nested.a.b[0]").unwrap(), vec!["1"]);
+        assert_eq!(run(".nested.a.b[-1]").unwrap(), vec!["3"]);
+        assert_eq!(run(".nested.a.b[ 1 ]").unwrap(), vec!["2"]);
+    }
+
+    #[test]
+    fn quoted_keys() {
+        assert_eq!(run(".\"with space\"").unwrap(), vec!["true"]);
+        assert_eq!(run(".[\"with space\"]").unwrap(), vec!["true"]);
+        assert_eq!(run(".users[0].\"first name\"").unwrap(), vec!["\"A\""]);
+        assert_eq!(run(".nested[\"a\"].b[0]").unwrap(), vec!["1"]);
+    }
+
+    #[test]
+    fn missing_key_is_null() {
+        assert_eq!(run(".missing").unwrap(), vec!["null"]);
+        assert_eq!(run(".missing.deeper").unwrap(), vec!["null"]);
+    }
+
+    #[test]
+    fn out_of_range_index_is_null() {
+        assert_eq!(run(".nested.a.b[3]").unwrap(), vec!["null"]);
+        assert_eq!(run(".nested.a.b[-4]").unwrap(), vec!["null"]);
+    }
+
+    #[test]
+    fn iterate_and_select() {
+        assert_eq!(
+            run(".users[] | select(.age > 30) | .name").unwrap(),
+            vec!["\"Alice\""]
+        );
+        assert_eq!(run(".users[].age").unwrap(), vec!["42", "27"]);
+        assert_eq!(
+            run(".users[] | select(.name == \"Bob\") | .age").unwrap(),
+            vec!["27"]
+        );
+        assert_eq!(run(".users | length").unwrap(), vec!["2"]);
+        assert_eq!(run(".nested | keys").unwrap(), vec!["[\"a\"]"]);
+    }
+
+    #[test]
+    fn malformed_paths() {
+        assert_eq!(
+            run("users").unwrap_err(),
+            "[Invalid Query] 'users' is not a valid path, paths start with '.'"
+        );
+        assert_eq!(
+            run(".users[0").unwrap_err(),
+            "[Invalid Query] missing ']' in '.users[0'"
+        );
+        assert_eq!(
+            run(".users.!").unwrap_err(),
+            "[Invalid Query] expected a field name after '.' in '.users.!'"
+        );
+        assert_eq!(
+            run(".users name").unwrap_err(),
+            "[Invalid Query] unexpected ' name' in '.users name'"
+        );
+        assert_eq!(
+            run(".users[name]").unwrap_err(),
+            "[Invalid Query] 'name' is not a static DATEX value"
+        );
+    }
+
+    #[test]
+    fn invalid_access() {
+        assert_eq!(
+            run(".users[\"name\"]").unwrap_err(),
+            "[Query Error] can not index a list with \"name\""
+        );
+        assert_eq!(
+            run(".nested.a.b[0].c").unwrap_err(),
+            "[Query Error] can not index 1 with \"c\""
+        );
+        assert_eq!(
+            run(".users[0].age[]").unwrap_err(),
+            "[Query Error] can not iterate over 42"
+        );
+    }
+}
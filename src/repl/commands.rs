@@ -0,0 +1,213 @@
+use super::ReplCommand;
+use super::settings::Setting;
+use datex_core::values::core_values::endpoint::Endpoint;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+/// Action of a REPL command
+pub enum CommandAction {
+    /// clears the screen, handled in the input loop
+    Clear,
+    /// prints the command list, handled in the input loop
+    Help,
+    /// sent to the runtime
+    Runtime(ReplCommand),
+}
+
+/// A REPL command, entered with a `.` prefix (e.g. `.help`)
+pub struct Command {
+    pub name: &'static str,
+    /// argument syntax shown in the help, empty if the command has no arguments
+    pub args: &'static str,
+    pub help: &'static str,
+    /// parses the (trimmed) arguments of the command
+    parse: fn(&str) -> Result<CommandAction, String>,
+}
+
+/// All available REPL commands
+pub const COMMANDS: &[Command] = &[
+    Command {
+        name: "help",
+        args: "",
+        help: "Show all REPL commands",
+        parse: |args| no_args(args, CommandAction::Help),
+    },
+    Command {
+        name: "clear",
+        args: "",
+        help: "Clear the screen",
+        parse: |args| no_args(args, CommandAction::Clear),
+    },
+    Command {
+        name: "com",
+        args: "",
+        help: "Show the ComHub interfaces and sockets",
+        parse: |args| no_args(args, CommandAction::Runtime(ReplCommand::ComHubInfo)),
+    },
+    Command {
+        name: "mem",
+        args: "",
+        help: "Show a dump of the local memory",
+        parse: |args| no_args(args, CommandAction::Runtime(ReplCommand::LocalMemoryDump)),
+    },
+    Command {
+        name: "trace",
+        args: "<endpoint>",
+        help: "Trace the network route to an endpoint",
+        parse: |args| {
+            if args.is_empty() {
+                return Err("missing endpoint".to_string());
+            }
+            let endpoint =
+                Endpoint::from_str(args).map_err(|_| format!("invalid endpoint '{args}'"))?;
+            Ok(CommandAction::Runtime(ReplCommand::Trace(endpoint)))
+        },
+    },
+    Command {
+        name: "set",
+        args: "[<setting> <value>]",
+        help: "Show the display settings or change a setting",
+        parse: |args| {
+            if args.is_empty() {
+                return Ok(CommandAction::Runtime(ReplCommand::Set(None)));
+            }
+            let (name, value) = args
+                .split_once(char::is_whitespace)
+                .ok_or_else(|| "missing value".to_string())?;
+            let setting = Setting::parse(name, value.trim())?;
+            Ok(CommandAction::Runtime(ReplCommand::Set(Some(setting))))
+        },
+    },
+    Command {
+        name: "bytecode",
+        args: "<script>",
+        help: "Show the disassembled bytecode of a script without executing it",
+        parse: |args| {
+            let script = script_arg(args)?;
+            Ok(CommandAction::Runtime(ReplCommand::Bytecode(script)))
+        },
+    },
+    Command {
+        name: "time",
+        args: "<script>",
+        help: "Execute a script and show the compile time, execution time and bytecode size",
+        parse: |args| {
+            let script = script_arg(args)?;
+            Ok(CommandAction::Runtime(ReplCommand::Time(script)))
+        },
+    },
+    Command {
+        name: "load",
+        args: "<file>",
+        help: "Execute a DATEX file in the current session, so its declarations can be used",
+        parse: |args| {
+            let path = file_arg(args)?;
+            Ok(CommandAction::Runtime(ReplCommand::Load(path)))
+        },
+    },
+    Command {
+        name: "save",
+        args: "<file>",
+        help: "Save the successfully executed inputs of the session as a DATEX file",
+        parse: |args| {
+            let path = file_arg(args)?;
+            Ok(CommandAction::Runtime(ReplCommand::Save(path)))
+        },
+    },
+];
+
+fn script_arg(args: &str) -> Result<String, String> {
+    if args.is_empty() {
+        Err("missing script".to_string())
+    } else {
+        Ok(args.to_string())
+    }
+}
+
+fn file_arg(args: &str) -> Result<PathBuf, String> {
+    if args.is_empty() {
+        Err("missing file".to_string())
+    } else {
+        Ok(PathBuf::from(args))
+    }
+}
+
+fn no_args(args: &str, action: CommandAction) -> Result<CommandAction, String> {
+    if args.is_empty() {
+        Ok(action)
+    } else {
+        Err("the command has no arguments".to_string())
+    }
+}
+
+impl Command {
+    /// Returns the usage of the command, e.g. `.trace <endpoint>`
+    pub fn usage(&self) -> String {
+        if self.args.is_empty() {
+            format!(".{}", self.name)
+        } else {
+            format!(".{} {}", self.name, self.args)
+        }
+    }
+}
+
+/// Returns true if the input is a REPL command and not a DATEX script.
+/// Commands start with a `.` followed by a letter, so decimals like `.5` are still executed.
+pub fn is_command(input: &str) -> bool {
+    let mut chars = input.trim_start().chars();
+    chars.next() == Some('.') && chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+}
+
+/// Parses a REPL command input, returning an error for unknown commands or invalid arguments
+pub fn parse_command(input: &str) -> Result<CommandAction, String> {
+    let input = input.trim();
+    let input = input.strip_prefix('.').unwrap_or(input);
+    let (name, args) = input.split_once(char::is_whitespace).unwrap_or((input, ""));
+    let command = COMMANDS
+        .iter()
+        .find(|command| command.name == name)
+        .ok_or_else(|| format!("unknown command '.{name}', use .help to list all commands"))?;
+    (command.parse)(args.trim()).map_err(|e| format!("{e}, usage: {}", command.usage()))
+}
+
+/// Returns the help text listing all commands
+pub fn help_text() -> String {
+    let width = COMMANDS
+        .iter()
+        .map(|command| command.usage().len())
+        .max()
+        .unwrap_or(0);
+    COMMANDS
+        .iter()
+        .map(|command| format!("{:width$}  {}", command.usage(), command.help))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_commands_with_a_single_dot() {
+        assert!(matches!(parse_command(".help"), Ok(CommandAction::Help)));
+        assert!(matches!(
+            parse_command("  .clear "),
+            Ok(CommandAction::Clear)
+        ));
+        assert!(parse_command("..help").is_err());
+    }
+
+    #[test]
+    fn help_text_matches_the_readme() {
+        let readme = include_str!("../../README.md");
+        for command in COMMANDS {
+            let row = format!("| `{}`", command.usage());
+            let line = readme
+                .lines()
+                .find(|line| line.starts_with(&row))
+                .unwrap_or_else(|| panic!("{} is not documented", command.usage()));
+            assert!(line.contains(command.help), "{line}");
+        }
+    }
+}
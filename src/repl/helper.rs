@@ -0,0 +1,420 @@
+use super::commands::COMMANDS;
+use crate::utils::output::compact_source;
+use datex_core::ast::lexer::Token;
+use datex_core::decompiler::apply_syntax_highlighting;
+use datex_core::network::com_hub_metadata::ComHubMetadata;
+use datex_core::values::value_container::ValueContainer;
+use logos::Logos;
+use rustyline::completion::Completer;
+use rustyline::highlight::{CmdKind, Highlighter};
+use rustyline::hint::{Hint, Hinter, HistoryHinter};
+use rustyline::validate::{ValidationContext, ValidationResult, Validator};
+use rustyline::{
+    Cmd, ConditionalEventHandler, Context, Event, EventContext, Helper, Movement, RepeatCount,
+};
+use std::borrow::Cow;
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::{Arc, Mutex};
+
+/// DATEX keywords offered as completions
+const KEYWORDS: [&str; 12] = [
+    "const", "var", "mut", "final", "function", "if", "else", "is", "matches", "true", "false",
+    "null",
+];
+
+/// Maximum length of the value in a variable preview hint
+const MAX_PREVIEW_LENGTH: usize = 40;
+
+/// Session data for completions and hints, updated by the REPL after each command
+#[derive(Debug, Default)]
+pub struct CompletionData {
+    /// variables declared in the execution context of the session
+    pub variables: BTreeSet<String>,
+    /// own endpoint and endpoints known to the ComHub
+    pub endpoints: BTreeSet<String>,
+    /// type and compact value of variables, shown as hint for a bare variable name
+    pub value_previews: BTreeMap<String, String>,
+}
+
+impl CompletionData {
+    /// Adds the variables declared with `const` or `var` in a successfully executed script
+    pub fn add_declared_variables(&mut self, script: &str) {
+        let mut tokens = Token::lexer(script)
+            .filter_map(Result::ok)
+            .filter(|token| !matches!(token, Token::Whitespace));
+        while let Some(token) = tokens.next() {
+            if matches!(token, Token::Const | Token::Variable) {
+                let name = tokens.find(|token| !matches!(token, Token::Mutable | Token::Final));
+                if let Some(Token::Identifier(name)) = name {
+                    self.variables.insert(name);
+                }
+            }
+        }
+    }
+
+    /// Returns the declared variables that are referenced in a script,
+    /// which may have changed their value
+    pub fn referenced_variables(&self, script: &str) -> BTreeSet<String> {
+        Token::lexer(script)
+            .filter_map(|token| match token {
+                Ok(Token::Identifier(name)) if self.variables.contains(&name) => Some(name),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Stores the type and the compact value of a variable as a preview,
+    /// e.g. `: integer = 42`
+    pub fn set_value_preview(&mut self, name: String, value: &ValueContainer) {
+        let mut compact = compact_source(value);
+        if compact.chars().count() > MAX_PREVIEW_LENGTH {
+            compact = compact.chars().take(MAX_PREVIEW_LENGTH - 1).collect();
+            compact.push('…');
+        }
+        let preview = format!(": {} = {compact}", value.actual_type());
+        self.value_previews.insert(name, preview);
+    }
+
+    /// Replaces the known endpoints with the endpoints from the ComHub metadata
+    pub fn update_endpoints(&mut self, metadata: &ComHubMetadata) {
+        self.endpoints = metadata
+            .endpoint_sockets
+            .keys()
+            .chain([&metadata.endpoint])
+            .map(|endpoint| endpoint.to_string())
+            .collect();
+    }
+}
+
+pub struct DatexSyntaxHelper {
+    pub completion_data: Arc<Mutex<CompletionData>>,
+}
+
+/// Indentation inserted at the start of continuation lines of incomplete input.
+/// It is displayed as the `..` prompt by the highlighter, so the cursor position
+/// computed by rustyline still matches the displayed input.
+const CONTINUATION_INDENT: &str = "  ";
+
+/// Returns true if the input has unclosed brackets, strings or block comments
+fn is_incomplete(input: &str) -> bool {
+    let mut depth = 0;
+    let mut chars = input.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' | '\'' => {
+                let mut closed = false;
+                while let Some(next) = chars.next() {
+                    match next {
+                        '\\' => {
+                            chars.next();
+                        }
+                        _ if next == c => {
+                            closed = true;
+                            break;
+                        }
+                        _ => {}
+                    }
+                }
+                if !closed {
+                    return true;
+                }
+            }
+            '/' if chars.peek() == Some(&'/') => {
+                while chars.next_if(|&next| next != '\n').is_some() {}
+            }
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                let mut closed = false;
+                let mut previous = None;
+                for next in chars.by_ref() {
+                    if previous == Some('*') && next == '/' {
+                        closed = true;
+                        break;
+                    }
+                    previous = Some(next);
+                }
+                if !closed {
+                    return true;
+                }
+            }
+            '{' | '(' | '[' => depth += 1,
+            '}' | ')' | ']' => depth -= 1,
+            _ => {}
+        }
+    }
+    depth > 0
+}
+
+/// Replaces the continuation indentation of all lines after the first with a dimmed `..` prompt
+fn render_continuation_prompts(highlighted: &str) -> String {
+    highlighted
+        .split('\n')
+        .enumerate()
+        .map(|(i, line)| {
+            if i == 0 {
+                return line.to_string();
+            }
+            // skip the color escape sequences at the start of the line
+            let mut start = 0;
+            while line[start..].starts_with("\x1b[") {
+                match line[start..].find('m') {
+                    Some(end) => start += end + 1,
+                    None => break,
+                }
+            }
+            match line[start..].strip_prefix(CONTINUATION_INDENT) {
+                Some(rest) => format!("\x1b[2m..\x1b[0m{}{rest}", &line[..start]),
+                None => line.to_string(),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Inserts a continuation line instead of submitting the input if it is incomplete
+pub struct ContinuationHandler;
+
+impl ConditionalEventHandler for ContinuationHandler {
+    fn handle(
+        &self,
+        _evt: &Event,
+        _n: RepeatCount,
+        _positive: bool,
+        ctx: &EventContext,
+    ) -> Option<Cmd> {
+        if is_incomplete(ctx.line()) {
+            Some(Cmd::Insert(1, format!("\n{CONTINUATION_INDENT}")))
+        } else {
+            None
+        }
+    }
+}
+
+/// Clears the input on Ctrl+C instead of interrupting the prompt if the input is not empty
+pub struct InterruptHandler;
+
+impl ConditionalEventHandler for InterruptHandler {
+    fn handle(
+        &self,
+        _evt: &Event,
+        _n: RepeatCount,
+        _positive: bool,
+        ctx: &EventContext,
+    ) -> Option<Cmd> {
+        if ctx.line().is_empty() {
+            None
+        } else {
+            Some(Cmd::Kill(Movement::WholeBuffer))
+        }
+    }
+}
+
+impl Highlighter for DatexSyntaxHelper {
+    fn highlight<'l>(&self, line: &'l str, _pos: usize) -> std::borrow::Cow<'l, str> {
+        let highlighted = apply_syntax_highlighting(line.to_string()).unwrap();
+        std::borrow::Cow::Owned(render_continuation_prompts(&highlighted))
+    }
+    fn highlight_hint<'h>(&self, hint: &'h str) -> Cow<'h, str> {
+        Cow::Owned(format!("\x1b[2m{hint}\x1b[0m"))
+    }
+    fn highlight_char(&self, _line: &str, _pos: usize, _kind: CmdKind) -> bool {
+        true
+    }
+}
+
+// ref x = {}
+// val x = (1,2,3,r);
+// val y: ((string|decimal): number)  = ("sadf":234)
+// const val x = 10;
+// ref x = {};
+// x.a = 10;
+// ref y = (1,2,3); // Map
+// y.x = 10;
+// func (1,2,3)
+
+// ref weather: Weather;
+// weather = getWeatherFromApi(); -> val
+// weather = always cpnvertWearth(getWeatherFromApi()); -> indirect copy
+
+// ref user: User; <-- $user
+// #0 <- $user
+// for name in endpoint (
+//    user = resolveInner/innerRef/collapse/resolve getUserFromApi(name); $a -> $b -> $c;
+// )
+// user // <- $x
+// val x = 10;
+
+// ref x = weather;
+
+// (1: x) == ($(1): x, 1: x)
+// (val string: any)
+// {x: 1} == {0: x, (0min): 20m}
+// x.y  -> (y: 34)
+// x.({a}) -> ({a}: 4)
+
+impl Validator for DatexSyntaxHelper {
+    fn validate(&self, ctx: &mut ValidationContext) -> rustyline::Result<ValidationResult> {
+        if is_incomplete(ctx.input()) {
+            Ok(ValidationResult::Incomplete)
+        } else {
+            Ok(ValidationResult::Valid(None))
+        }
+    }
+    fn validate_while_typing(&self) -> bool {
+        true
+    }
+}
+impl Completer for DatexSyntaxHelper {
+    type Candidate = String;
+
+    fn complete(
+        &self,
+        line: &str,
+        pos: usize,
+        _ctx: &Context<'_>,
+    ) -> rustyline::Result<(usize, Vec<String>)> {
+        let before = &line[..pos];
+        // complete the command name of a REPL command
+        let input = before.trim_start();
+        if input.starts_with('.') && !input.contains(char::is_whitespace) {
+            let candidates = COMMANDS
+                .iter()
+                .map(|command| format!(".{}", command.name))
+                .filter(|name| name.starts_with(input) && name != input)
+                .collect();
+            return Ok((pos - input.len(), candidates));
+        }
+        // endpoints can contain '@', '+' and '-', identifiers only alphanumeric characters and '_'
+        let is_word_char = |c: char| c.is_alphanumeric() || c == '_';
+        let mut start = before
+            .rfind(|c: char| !(is_word_char(c) || matches!(c, '@' | '+' | '-')))
+            .map(|i| i + 1)
+            .unwrap_or(0);
+        if !before[start..].starts_with('@') {
+            start = before
+                .rfind(|c: char| !is_word_char(c))
+                .map(|i| i + 1)
+                .unwrap_or(0);
+        }
+        let word = &before[start..];
+        if word.is_empty() {
+            return Ok((pos, vec![]));
+        }
+
+        let data = self.completion_data.lock().unwrap();
+        let mut candidates = BTreeSet::new();
+        if word.starts_with('@') {
+            candidates.extend(data.endpoints.iter().cloned());
+        } else {
+            candidates.extend(KEYWORDS.iter().map(|k| k.to_string()));
+            candidates.extend(data.variables.iter().cloned());
+        }
+        let candidates = candidates
+            .into_iter()
+            .filter(|candidate| candidate.starts_with(word) && candidate != word)
+            .collect();
+        Ok((start, candidates))
+    }
+}
+/// Inline hint shown after the cursor
+pub enum DatexHint {
+    /// rest of a previous input from the history, accepted with the right arrow key
+    History(String),
+    /// type and value of a variable, only displayed
+    ValuePreview(String),
+}
+
+impl Hint for DatexHint {
+    fn display(&self) -> &str {
+        match self {
+            DatexHint::History(hint) | DatexHint::ValuePreview(hint) => hint,
+        }
+    }
+    fn completion(&self) -> Option<&str> {
+        match self {
+            DatexHint::History(hint) => Some(hint),
+            DatexHint::ValuePreview(_) => None,
+        }
+    }
+}
+
+impl Hinter for DatexSyntaxHelper {
+    type Hint = DatexHint;
+
+    fn hint(&self, line: &str, pos: usize, ctx: &Context<'_>) -> Option<DatexHint> {
+        if pos < line.len() {
+            return None;
+        }
+        let preview = self
+            .completion_data
+            .lock()
+            .unwrap()
+            .value_previews
+            .get(line.trim())
+            .cloned();
+        match preview {
+            Some(preview) => Some(DatexHint::ValuePreview(preview)),
+            None => HistoryHinter::new()
+                .hint(line, pos, ctx)
+                .map(DatexHint::History),
+        }
+    }
+}
+impl Helper for DatexSyntaxHelper {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn complete_input() {
+        assert!(!is_incomplete(""));
+        assert!(!is_incomplete("1 + 2"));
+        assert!(!is_incomplete("{a: [1, (2)]}"));
+        assert!(!is_incomplete("const x = {\n  a: 1\n}"));
+    }
+
+    #[test]
+    fn unclosed_brackets() {
+        assert!(is_incomplete("("));
+        assert!(is_incomplete("[1, 2"));
+        assert!(is_incomplete("{a: 1"));
+        assert!(is_incomplete("{a: [1, 2]"));
+        assert!(is_incomplete("{\n  a: (1 + 2)\n"));
+    }
+
+    #[test]
+    fn brackets_inside_strings() {
+        assert!(!is_incomplete("\"(\""));
+        assert!(!is_incomplete("'{['"));
+        assert!(!is_incomplete("[\"]\"]"));
+        assert!(is_incomplete("[\"]\""));
+        assert!(!is_incomplete(r#""escaped \" quote (""#));
+    }
+
+    #[test]
+    fn unclosed_strings() {
+        assert!(is_incomplete("\"text"));
+        assert!(is_incomplete("'text"));
+        assert!(is_incomplete(r#""escaped \""#));
+    }
+
+    #[test]
+    fn comments() {
+        assert!(!is_incomplete("1 // ("));
+        assert!(is_incomplete("( // )"));
+        assert!(!is_incomplete("( // comment\n)"));
+        assert!(!is_incomplete("1 /* ( */"));
+        assert!(is_incomplete("1 /* comment"));
+        assert!(is_incomplete("[1 /* ] */"));
+        assert!(!is_incomplete("\"// not a comment\""));
+    }
+
+    #[test]
+    fn extra_closing_brackets_are_submitted() {
+        // the compiler reports the error instead of waiting for more input
+        assert!(!is_incomplete(")"));
+        assert!(!is_incomplete("[1]]"));
+        assert!(!is_incomplete("{a: 1}}"));
+    }
+}
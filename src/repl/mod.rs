@@ -0,0 +1,653 @@
+use crate::disasm::{DisasmError, disassemble};
+use crate::utils::config::{
+    ConfigError, create_runtime_with_config, get_config_path, get_history_path,
+};
+use crate::utils::errors::{CommandError, EXIT_CODE_ERROR};
+use crate::utils::execution::EvaluationError;
+use crate::utils::output::{OutputFormat, print_warning};
+use datex_core::crypto::crypto_native::CryptoNative;
+use datex_core::global::protocol_structures::instructions::{Instruction, SlotAddress};
+use datex_core::logger::init_logger_debug;
+use datex_core::run_async;
+use datex_core::runtime::execution_context::{ExecutionContext, ScriptExecutionError};
+use datex_core::runtime::global_context::{GlobalContext, set_global_context};
+use datex_core::runtime::{Runtime, RuntimeInternal};
+use datex_core::utils::time_native::TimeNative;
+use datex_core::values::core_values::endpoint::Endpoint;
+use datex_core::values::value_container::ValueContainer;
+use rustyline::error::ReadlineError;
+use rustyline::{EventHandler, KeyCode, KeyEvent, Modifiers};
+use std::fmt::Display;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::thread::spawn;
+use std::time::{Duration, Instant};
+
+mod commands;
+mod helper;
+mod results;
+mod settings;
+
+/// Maximum number of entries kept in the REPL history file
+const MAX_HISTORY_SIZE: usize = 1000;
+
+/// Returns the config of the REPL editor.
+/// The history file is limited to [MAX_HISTORY_SIZE] entries by rustyline,
+/// which truncates the file to the maximum history size in `append_history`.
+fn editor_config() -> rustyline::Result<rustyline::Config> {
+    Ok(rustyline::Config::builder()
+        .max_history_size(MAX_HISTORY_SIZE)?
+        .auto_add_history(true)
+        .bracketed_paste(true)
+        .build())
+}
+
+use commands::{CommandAction, help_text, is_command, parse_command};
+use helper::{CompletionData, ContinuationHandler, DatexSyntaxHelper, InterruptHandler};
+use results::{Results, ScriptWithResults};
+use settings::{ReplSettings, Setting};
+
+#[derive(Debug, Clone, Default)]
+pub struct ReplOptions {
+    pub verbose: bool,
+    pub config_path: Option<PathBuf>,
+    pub output_format: OutputFormat,
+}
+
+#[derive(Debug)]
+pub enum ReplError {
+    ReadlineError(ReadlineError),
+    ConfigError(ConfigError),
+}
+
+impl Display for ReplError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReplError::ReadlineError(e) => write!(f, "[Readline Error] {e}"),
+            ReplError::ConfigError(e) => write!(f, "[Config Error] {e}"),
+        }
+    }
+}
+
+impl CommandError for ReplError {
+    fn exit_code(&self) -> i32 {
+        EXIT_CODE_ERROR
+    }
+}
+
+impl From<ReadlineError> for ReplError {
+    fn from(err: ReadlineError) -> Self {
+        ReplError::ReadlineError(err)
+    }
+}
+impl From<ConfigError> for ReplError {
+    fn from(err: ConfigError) -> Self {
+        ReplError::ConfigError(err)
+    }
+}
+
+pub async fn repl(options: ReplOptions) -> Result<(), ReplError> {
+    set_global_context(GlobalContext::new(
+        Arc::new(CryptoNative),
+        Arc::new(TimeNative),
+    ));
+
+    let (cmd_sender, mut cmd_receiver) = tokio::sync::mpsc::channel::<ReplCommand>(100);
+    let (response_sender, response_receiver) = tokio::sync::mpsc::channel::<ReplResponse>(100);
+    let completion_data = Arc::new(Mutex::new(CompletionData::default()));
+
+    // resolved once, so that the settings and the runtime use the same config file
+    let config_path = get_config_path(options.config_path)?;
+    let settings = ReplSettings::new(options.output_format, config_path.as_ref(), options.verbose);
+    // initialize the logger with all levels before the runtime, so that `.set verbose`
+    // can change the log level at any time
+    init_logger_debug();
+    settings.apply_log_level();
+
+    run_async! {
+        let runtime = create_runtime_with_config(config_path, settings.verbose).await?;
+        print_banner(&runtime);
+
+        completion_data.lock().unwrap().update_endpoints(&runtime.com_hub().get_metadata());
+        let history_path = get_history_path(&runtime.endpoint()).unwrap_or_else(|err| {
+            print_warning(&format!("Could not create the history directory: {err:?}"));
+            None
+        });
+        repl_loop(cmd_sender, response_receiver, completion_data.clone(), history_path)?;
+
+        let mut session = ReplSession::new(completion_data.clone(), options.output_format, settings);
+
+        while let Some(command) = cmd_receiver.recv().await {
+            // Ctrl+C while a command is running cancels it and returns to the prompt
+            let response = session
+                .handle_command_or_cancel(&runtime, command, tokio::signal::ctrl_c())
+                .await;
+            response_sender.send(response).await.unwrap();
+
+            // endpoints may have connected or disconnected in the meantime
+            completion_data.lock().unwrap().update_endpoints(&runtime.com_hub().get_metadata());
+        }
+
+        // the input loop has ended with Ctrl+D
+        shutdown_runtime(&runtime).await;
+
+        Ok(())
+    }
+}
+
+/// State of a REPL session in the runtime thread
+struct ReplSession {
+    execution_context: ExecutionContext,
+    completion_data: Arc<Mutex<CompletionData>>,
+    /// results of previous inputs, accessible as `_`, `_1`, `_2`, …
+    results: Results,
+    /// successfully executed inputs, saved with `.save`
+    inputs: Vec<String>,
+    output_format: OutputFormat,
+    settings: ReplSettings,
+}
+
+/// Runs a future until it completes, or returns `None` if `cancel` completes first
+async fn cancellable<R, T>(
+    future: impl Future<Output = R>,
+    cancel: impl Future<Output = T>,
+) -> Option<R> {
+    tokio::select! {
+        result = future => Some(result),
+        _ = cancel => None,
+    }
+}
+
+impl ReplSession {
+    fn new(
+        completion_data: Arc<Mutex<CompletionData>>,
+        output_format: OutputFormat,
+        settings: ReplSettings,
+    ) -> Self {
+        ReplSession {
+            // the bytecode is shown by the session if verbose
+            execution_context: ExecutionContext::local(),
+            completion_data,
+            results: Results::default(),
+            inputs: vec![],
+            output_format,
+            settings,
+        }
+    }
+
+    /// Handles a command like [ReplSession::handle_command], but cancels it once `cancel`
+    /// completes. A cancelled command does not store a result, input or declaration.
+    async fn handle_command_or_cancel<T>(
+        &mut self,
+        runtime: &Runtime,
+        command: ReplCommand,
+        cancel: impl Future<Output = T>,
+    ) -> ReplResponse {
+        // a cancelled script may already be compiled, its declarations are removed by
+        // restoring the compile scope, as they refer to slots that were never allocated
+        let execution_context = self.execution_context.clone();
+        match cancellable(self.handle_command(runtime, command), cancel).await {
+            Some(response) => response,
+            None => {
+                self.execution_context = execution_context;
+                ReplResponse::Error("\x1b[33mCancelled\x1b[0m".to_string())
+            }
+        }
+    }
+
+    /// Handles a command from the input loop and returns the response
+    async fn handle_command(&mut self, runtime: &Runtime, command: ReplCommand) -> ReplResponse {
+        match command {
+            ReplCommand::ComHubInfo => {
+                ReplResponse::Result(Some(runtime.com_hub().get_metadata().to_string()))
+            }
+            ReplCommand::LocalMemoryDump => match self.execution_context.memory_dump() {
+                Some(metadata) => ReplResponse::Result(Some(format!("Memory Dump:\n\n{metadata}"))),
+                None => ReplResponse::Result(Some("<Memory dump not available>".to_string())),
+            },
+            ReplCommand::Trace(endpoint) => match runtime.com_hub().record_trace(endpoint).await {
+                Some(trace) => ReplResponse::Result(Some(trace.to_string())),
+                None => ReplResponse::Result(Some("Could not create trace".to_string())),
+            },
+            ReplCommand::Set(Some(setting)) => {
+                self.settings.set(setting);
+                ReplResponse::Result(Some(self.settings.to_string()))
+            }
+            ReplCommand::Set(None) => ReplResponse::Result(Some(self.settings.to_string())),
+            ReplCommand::Execute(line) => self.execute(runtime, &line, false).await,
+            ReplCommand::Time(script) => self.execute(runtime, &script, true).await,
+            ReplCommand::Bytecode(script) => self.bytecode(&script),
+            ReplCommand::Load(path) => match std::fs::read_to_string(&path) {
+                Ok(script) => self.execute(runtime, &script, false).await,
+                Err(e) => ReplResponse::Error(format!("\x1b[31m[IO Error] {e}\x1b[0m")),
+            },
+            ReplCommand::Save(path) => match std::fs::write(&path, session_script(&self.inputs)) {
+                Ok(()) => ReplResponse::Result(Some(format!(
+                    "Saved {} input(s) to {}",
+                    self.inputs.len(),
+                    path.display()
+                ))),
+                Err(e) => ReplResponse::Error(format!("\x1b[31m[IO Error] {e}\x1b[0m")),
+            },
+        }
+    }
+
+    /// Executes a script in the REPL execution context and returns the formatted result or error.
+    /// Variables declared or referenced in a successfully executed script are updated
+    /// for completions and hints. If `timed` is set, the compile and execution times
+    /// and the bytecode size are appended to the result.
+    async fn execute(&mut self, runtime: &Runtime, script: &str, timed: bool) -> ReplResponse {
+        let variables = self.completion_data.lock().unwrap().variables.clone();
+        let script_with_results = self.results.insert_results(script, &variables);
+        let result = self
+            .execute_and_store_result(runtime, &script_with_results)
+            .await;
+
+        if result.is_ok() {
+            self.inputs.push(script_with_results.source);
+            let mut completion_data = self.completion_data.lock().unwrap();
+            completion_data.add_declared_variables(script);
+            // update the value previews of all variables that may have changed
+            for name in completion_data.referenced_variables(script) {
+                if let Some(value) = self.variable_value(&name) {
+                    completion_data.set_value_preview(name, &value);
+                }
+            }
+        }
+
+        match result {
+            Err(EvaluationError::ScriptExecutionError(ScriptExecutionError::CompilerError(e))) => {
+                ReplResponse::Error(format!("\x1b[31m[Compiler Error] {e}\x1b[0m"))
+            }
+            Err(EvaluationError::ScriptExecutionError(ScriptExecutionError::ExecutionError(e))) => {
+                ReplResponse::Error(format!("\x1b[31m[Execution Error] {e}\x1b[0m"))
+            }
+            Err(EvaluationError::OutputError(e)) => {
+                ReplResponse::Error(format!("\x1b[31m[Output Error] {e}\x1b[0m"))
+            }
+            Ok((result, stats)) => {
+                let mut lines = vec![];
+                if let Some((index, formatted_value)) = result {
+                    lines.push(format_result(index, &formatted_value));
+                }
+                if timed {
+                    lines.push(format!("\x1b[2m{stats}\x1b[0m"));
+                }
+                ReplResponse::Result((!lines.is_empty()).then(|| lines.join("\n")))
+            }
+        }
+    }
+
+    /// Returns the current value of a variable, read from the memory of the execution context.
+    /// The variable name is only compiled to find its slot, nothing is executed.
+    fn variable_value(&self, name: &str) -> Option<ValueContainer> {
+        // compile with a copy of the context, so that the session is not changed
+        let dxb = self.execution_context.clone().compile(name, &[]).ok()?;
+        let instructions = disassemble(&dxb).ok()?;
+        let [instruction] = instructions.as_slice() else {
+            return None;
+        };
+        let Instruction::GetSlot(SlotAddress(address)) = instruction.instruction else {
+            return None;
+        };
+        self.execution_context
+            .memory_dump()?
+            .slots
+            .into_iter()
+            .find(|(slot, _)| *slot == address)?
+            .1
+    }
+
+    /// Compiles a script without executing it and returns the disassembled bytecode
+    fn bytecode(&self, script: &str) -> ReplResponse {
+        let variables = self.completion_data.lock().unwrap().variables.clone();
+        let script = self.results.insert_results(script, &variables);
+        // compile with a copy of the context, so that declarations are not added to the session
+        let mut execution_context = self.execution_context.clone();
+        let dxb = match execution_context.compile(&script.script, &script.inserted_values) {
+            Ok(dxb) => dxb,
+            Err(e) => return ReplResponse::Error(format!("\x1b[31m[Compiler Error] {e}\x1b[0m")),
+        };
+        match disassembly(&dxb) {
+            Ok(disassembly) => ReplResponse::Result(Some(disassembly)),
+            Err(e) => ReplResponse::Error(format!("\x1b[31m{e}\x1b[0m")),
+        }
+    }
+
+    /// Compiles and executes a script and stores its result.
+    /// Returns the result number and the formatted result, and the execution statistics.
+    async fn execute_and_store_result(
+        &mut self,
+        runtime: &Runtime,
+        script: &ScriptWithResults,
+    ) -> Result<(Option<(usize, String)>, ExecutionStats), EvaluationError> {
+        let start = Instant::now();
+        let dxb = self
+            .execution_context
+            .compile(&script.script, &script.inserted_values)
+            .map_err(ScriptExecutionError::from)?;
+        let compile_time = start.elapsed();
+        let bytecode_size = dxb.len();
+        if self.settings.verbose {
+            match disassembly(&dxb) {
+                Ok(disassembly) => println!("\x1b[32m[Compiled Bytecode]\n{disassembly}\x1b[0m"),
+                Err(e) => println!("\x1b[31m{e}\x1b[0m"),
+            }
+        }
+
+        let start = Instant::now();
+        let result = runtime
+            .execute_dxb(dxb, Some(&mut self.execution_context), true)
+            .await
+            .map_err(ScriptExecutionError::from)?;
+        let stats = ExecutionStats {
+            compile_time,
+            execution_time: start.elapsed(),
+            bytecode_size,
+        };
+
+        let Some(result) = result else {
+            return Ok((None, stats));
+        };
+        let formatted_value = self.settings.format_value(&result, self.output_format)?;
+        Ok((Some((self.results.push(result), formatted_value)), stats))
+    }
+}
+
+/// Returns the disassembled instructions of DXB with the bytecode size
+fn disassembly(dxb: &[u8]) -> Result<String, DisasmError> {
+    let mut lines = vec![format!("; {} bytes", dxb.len())];
+    lines.extend(
+        disassemble(dxb)?
+            .iter()
+            .map(|instruction| instruction.to_string()),
+    );
+    Ok(lines.join("\n"))
+}
+
+/// Compile and execution times and bytecode size of a script, shown with `.time`
+struct ExecutionStats {
+    compile_time: Duration,
+    execution_time: Duration,
+    bytecode_size: usize,
+}
+
+impl Display for ExecutionStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "compiled in {:?} ({} bytes), executed in {:?}",
+            self.compile_time, self.bytecode_size, self.execution_time
+        )
+    }
+}
+
+/// Formats a result with its number before the `<`, e.g. `_1 < 42`
+fn format_result(index: usize, formatted_value: &str) -> String {
+    let prefix = format!("_{index} ");
+    // indent all lines except the first to match the REPL prompt indentation
+    let formatted_value = formatted_value
+        .lines()
+        .enumerate()
+        .map(|(i, line)| {
+            if i == 0 {
+                line.to_string()
+            } else {
+                format!("{}  {line}", " ".repeat(prefix.len()))
+            }
+        })
+        .collect::<Vec<String>>()
+        .join("\n");
+    format!("\x1b[2m{prefix}\x1b[0m< {formatted_value}")
+}
+
+/// Joins the executed inputs of a session into a replayable script.
+/// All inputs except the last one are terminated with `;`, so the script returns
+/// the result of the last input.
+fn session_script(inputs: &[String]) -> String {
+    let mut script = String::new();
+    for (i, input) in inputs.iter().enumerate() {
+        let input = input.trim();
+        script.push_str(input);
+        if i + 1 < inputs.len() && !input.ends_with(';') {
+            // a line comment at the end of the input would also comment out the `;`
+            if input.lines().last().is_some_and(|line| line.contains("//")) {
+                script.push('\n');
+            }
+            script.push(';');
+        }
+        script.push('\n');
+    }
+    script
+}
+
+/// Stops the update loop of the runtime and removes all interfaces from the ComHub,
+/// closing all connections of the runtime
+async fn shutdown_runtime(runtime: &Runtime) {
+    RuntimeInternal::stop_update_loop(runtime.internal()).await;
+    let interfaces = runtime
+        .com_hub()
+        .interfaces
+        .borrow()
+        .keys()
+        .cloned()
+        .collect::<Vec<_>>();
+    for interface in interfaces {
+        if let Err(err) = runtime.com_hub().remove_interface(interface).await {
+            print_warning(&format!("Could not close interface: {err:?}"));
+        }
+    }
+}
+
+fn print_banner(runtime: &Runtime) {
+    let cli_version = env!("CARGO_PKG_VERSION");
+
+    println!("================================================");
+    println!("DATEX REPL v{cli_version}");
+    println!("DATEX Core version: {}", runtime.version);
+    println!("Endpoint: {}", runtime.endpoint());
+    println!("\nexit using [CTRL + D], list commands with .help");
+    println!("================================================\n");
+}
+
+pub enum ReplCommand {
+    ComHubInfo,
+    LocalMemoryDump,
+    Trace(Endpoint),
+    Set(Option<Setting>),
+    Execute(String),
+    Time(String),
+    Bytecode(String),
+    Load(PathBuf),
+    Save(PathBuf),
+}
+
+enum ReplResponse {
+    Result(Option<String>),
+    Error(String),
+}
+
+fn repl_loop(
+    sender: tokio::sync::mpsc::Sender<ReplCommand>,
+    mut receiver: tokio::sync::mpsc::Receiver<ReplResponse>,
+    completion_data: Arc<Mutex<CompletionData>>,
+    history_path: Option<PathBuf>,
+) -> Result<(), ReplError> {
+    let mut rl = rustyline::Editor::<DatexSyntaxHelper, _>::with_config(editor_config()?)?;
+    rl.set_helper(Some(DatexSyntaxHelper { completion_data }));
+    if let Some(history_path) = &history_path {
+        // the history file does not exist before the first session
+        match rl.load_history(history_path) {
+            Err(ReadlineError::Io(err)) if err.kind() == std::io::ErrorKind::NotFound => {}
+            Err(err) => print_warning(&format!("Could not load the REPL history: {err}")),
+            Ok(()) => {}
+        }
+    }
+    rl.bind_sequence(
+        KeyEvent(KeyCode::Enter, Modifiers::NONE),
+        EventHandler::Conditional(Box::new(ContinuationHandler)),
+    );
+    rl.bind_sequence(
+        KeyEvent(KeyCode::Char('C'), Modifiers::CTRL),
+        EventHandler::Conditional(Box::new(InterruptHandler)),
+    );
+
+    spawn(move || {
+        loop {
+            let readline = rl.readline("> ");
+            match readline {
+                Ok(line) => {
+                    // append each input directly, so that the history is kept if the REPL is killed
+                    let saved = history_path.as_ref().map(|path| rl.append_history(path));
+                    if let Some(Err(err)) = saved {
+                        print_warning(&format!("Could not save the REPL history: {err}"));
+                    }
+                    if is_command(&line) {
+                        match parse_command(&line) {
+                            Ok(CommandAction::Clear) => {
+                                rl.clear_screen().unwrap();
+                                continue;
+                            }
+                            Ok(CommandAction::Help) => {
+                                println!("{}", help_text());
+                                continue;
+                            }
+                            Ok(CommandAction::Runtime(command)) => {
+                                sender.blocking_send(command).unwrap();
+                            }
+                            Err(e) => {
+                                eprintln!("\x1b[31m[Command Error] {e}\x1b[0m");
+                                continue;
+                            }
+                        }
+                    } else {
+                        sender
+                            .blocking_send(ReplCommand::Execute(line.clone()))
+                            .unwrap();
+                    }
+                }
+                Err(ReadlineError::Interrupted) => {
+                    // Ctrl+C on a non-empty prompt only clears the input, see InterruptHandler
+                    print_warning("(To exit, press Ctrl+D)");
+                    continue;
+                }
+                Err(_) => break,
+            }
+
+            let response = receiver.blocking_recv();
+            match response {
+                Some(ReplResponse::Result(result)) => {
+                    if let Some(result) = result {
+                        println!("{result}");
+                    }
+                }
+                Some(ReplResponse::Error(error)) => {
+                    eprintln!("{error}");
+                }
+                None => {
+                    break;
+                }
+            }
+        }
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use datex_core::runtime::RuntimeConfig;
+    use rustyline::history::{FileHistory, History};
+
+    #[test]
+    fn history_file_keeps_the_last_entries() {
+        let path = std::env::temp_dir().join(format!("datex-history-{}.txt", std::process::id()));
+        let mut rl =
+            rustyline::Editor::<(), FileHistory>::with_config(editor_config().unwrap()).unwrap();
+        for i in 0..MAX_HISTORY_SIZE + 10 {
+            rl.add_history_entry(format!("input {i}")).unwrap();
+            rl.append_history(&path).unwrap();
+        }
+        assert_eq!(rl.history().len(), MAX_HISTORY_SIZE);
+        let content = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        // the first line is the version header of the history file
+        let entries = content.lines().skip(1).collect::<Vec<_>>();
+        assert_eq!(entries.len(), MAX_HISTORY_SIZE);
+        assert_eq!(entries.first(), Some(&"input 10"));
+        assert_eq!(
+            entries.last(),
+            Some(&format!("input {}", MAX_HISTORY_SIZE + 9).as_str())
+        );
+    }
+
+    #[tokio::test]
+    async fn cancels_pending_futures() {
+        let pending = tokio::time::sleep(Duration::from_secs(3600));
+        assert_eq!(cancellable(pending, std::future::ready(())).await, None);
+        let ready = std::future::ready(42);
+        assert_eq!(
+            cancellable(ready, std::future::pending::<()>()).await,
+            Some(42)
+        );
+    }
+
+    fn response_text(response: ReplResponse) -> String {
+        match response {
+            ReplResponse::Result(result) => result.unwrap_or_default(),
+            ReplResponse::Error(error) => panic!("unexpected error: {error}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn session_is_usable_after_cancel() {
+        set_global_context(GlobalContext::new(
+            Arc::new(CryptoNative),
+            Arc::new(TimeNative),
+        ));
+        run_async! {
+            let runtime = Runtime::create_native(RuntimeConfig::default()).await;
+            let settings = ReplSettings::new(OutputFormat::DxCompact, None, false);
+            let completion_data = Arc::new(Mutex::new(CompletionData::default()));
+            let mut session = ReplSession::new(completion_data, OutputFormat::DxCompact, settings);
+
+            let result = session
+                .handle_command(&runtime, ReplCommand::Execute("40 + 2".to_string()))
+                .await;
+            assert!(response_text(result).contains("42"));
+
+            // the script is compiled in the first poll and then waits for the unknown
+            // endpoint, cancel completes in the second poll
+            let cancelled = session
+                .handle_command_or_cancel(
+                    &runtime,
+                    ReplCommand::Execute("const x = @unknown :: 1".to_string()),
+                    tokio::task::yield_now(),
+                )
+                .await;
+            assert!(matches!(cancelled, ReplResponse::Error(error) if error.contains("Cancelled")));
+
+            // the cancelled declaration is not kept
+            let result = session
+                .handle_command(&runtime, ReplCommand::Execute("x".to_string()))
+                .await;
+            assert!(matches!(result, ReplResponse::Error(error) if error.contains("Compiler Error")));
+            let result = session
+                .handle_command(&runtime, ReplCommand::Execute("const x = 2; x".to_string()))
+                .await;
+            assert!(response_text(result).contains("2"));
+
+            // the cancelled input is not stored, the next result is _3
+            let result = session
+                .handle_command(&runtime, ReplCommand::Execute("_1 + 1".to_string()))
+                .await;
+            let text = response_text(result);
+            assert!(text.contains("_3"), "{text}");
+            assert!(text.contains("43"), "{text}");
+
+            shutdown_runtime(&runtime).await;
+        }
+    }
+}
@@ -0,0 +1,178 @@
+use crate::utils::output::compact_source;
+use datex_core::ast::lexer::Token;
+use datex_core::values::value_container::ValueContainer;
+use logos::Logos;
+use std::collections::BTreeSet;
+
+/// A script with the previous results replaced, see [Results::insert_results]
+pub struct ScriptWithResults {
+    /// script with `?` placeholders for the used results
+    pub script: String,
+    /// results inserted for the placeholders
+    pub inserted_values: Vec<ValueContainer>,
+    /// script with the results as DATEX source, used for `.save`
+    pub source: String,
+}
+
+/// Results of previous inputs, accessible as `_` (last result) and `_1`, `_2`, … in later inputs
+#[derive(Default)]
+pub struct Results {
+    values: Vec<ValueContainer>,
+}
+
+impl Results {
+    /// Stores a result and returns its number
+    pub fn push(&mut self, value: ValueContainer) -> usize {
+        self.values.push(value);
+        self.values.len()
+    }
+
+    fn get(&self, name: &str) -> Option<&ValueContainer> {
+        match name {
+            "_" => self.values.last(),
+            _ => {
+                let index = name.strip_prefix('_')?.parse::<usize>().ok()?;
+                self.values.get(index.checked_sub(1)?)
+            }
+        }
+    }
+
+    /// Replaces all uses of `_` and `_<n>` in a script with placeholders for the results.
+    /// Map keys, properties, declarations and declared variables with the same name are not replaced.
+    pub fn insert_results(&self, script: &str, variables: &BTreeSet<String>) -> ScriptWithResults {
+        let tokens = Token::lexer(script)
+            .spanned()
+            .filter(|(token, _)| !matches!(token, Ok(Token::Whitespace)))
+            .collect::<Vec<_>>();
+
+        // placeholders in the input would be filled with the wrong results
+        let has_placeholders = tokens
+            .iter()
+            .any(|(token, _)| matches!(token, Ok(Token::Placeholder)));
+        if has_placeholders {
+            return ScriptWithResults {
+                script: script.to_string(),
+                inserted_values: vec![],
+                source: script.to_string(),
+            };
+        }
+
+        let mut result = ScriptWithResults {
+            script: String::new(),
+            inserted_values: vec![],
+            source: String::new(),
+        };
+        let mut end = 0;
+        for (i, (token, span)) in tokens.iter().enumerate() {
+            let Ok(Token::Identifier(name)) = token else {
+                continue;
+            };
+            let previous = i.checked_sub(1).and_then(|i| tokens[i].0.as_ref().ok());
+            let next = tokens.get(i + 1).and_then(|(token, _)| token.as_ref().ok());
+            if variables.contains(name)
+                || matches!(
+                    previous,
+                    Some(
+                        Token::Dot | Token::Const | Token::Variable | Token::Mutable | Token::Final
+                    )
+                )
+                || matches!(next, Some(Token::Colon))
+            {
+                continue;
+            }
+            let Some(value) = self.get(name) else {
+                continue;
+            };
+            let source = compact_source(value);
+            result.script.push_str(&script[end..span.start]);
+            result.script.push('?');
+            result.source.push_str(&script[end..span.start]);
+            result.source.push_str(&format!("({source})"));
+            result.inserted_values.push(value.clone());
+            end = span.end;
+        }
+        result.script.push_str(&script[end..]);
+        result.source.push_str(&script[end..]);
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use datex_core::values::core_values::integer::Integer;
+
+    fn results() -> Results {
+        let mut results = Results::default();
+        results.push(ValueContainer::from(Integer::from(1)));
+        results.push(ValueContainer::from("two"));
+        results
+    }
+
+    fn insert(script: &str) -> ScriptWithResults {
+        results().insert_results(script, &BTreeSet::new())
+    }
+
+    #[test]
+    fn inserts_last_and_numbered_results() {
+        let inserted = insert("[_, _1, _2]");
+        assert_eq!(inserted.script, "[?, ?, ?]");
+        assert_eq!(
+            inserted.inserted_values,
+            vec![
+                ValueContainer::from("two"),
+                ValueContainer::from(Integer::from(1)),
+                ValueContainer::from("two"),
+            ]
+        );
+        assert_eq!(inserted.source, "[(\"two\"), (1), (\"two\")]");
+    }
+
+    #[test]
+    fn keeps_other_identifiers() {
+        for script in ["_foo", "a_1", "_1a", "__1"] {
+            let inserted = insert(script);
+            assert_eq!(inserted.script, script);
+            assert!(inserted.inserted_values.is_empty());
+        }
+    }
+
+    #[test]
+    fn keeps_strings() {
+        let inserted = insert("\"_ and _1\" + _1");
+        assert_eq!(inserted.script, "\"_ and _1\" + ?");
+        assert_eq!(inserted.inserted_values.len(), 1);
+    }
+
+    #[test]
+    fn keeps_missing_results() {
+        for script in ["_0", "_3", "_99999999999999999999"] {
+            let inserted = insert(script);
+            assert_eq!(inserted.script, script);
+            assert!(inserted.inserted_values.is_empty());
+        }
+        let inserted = Results::default().insert_results("_", &BTreeSet::new());
+        assert_eq!(inserted.script, "_");
+    }
+
+    #[test]
+    fn keeps_declared_variables() {
+        let variables = BTreeSet::from(["_1".to_string()]);
+        let inserted = results().insert_results("_1 + _2", &variables);
+        assert_eq!(inserted.script, "_1 + ?");
+    }
+
+    #[test]
+    fn keeps_declarations_keys_and_properties() {
+        for script in ["const _1 = 5", "var _ = 5", "{_1: 5}", "x._1"] {
+            assert_eq!(insert(script).script, script);
+        }
+    }
+
+    #[test]
+    fn keeps_scripts_with_placeholders() {
+        let inserted = insert("? + _1");
+        assert_eq!(inserted.script, "? + _1");
+        assert!(inserted.inserted_values.is_empty());
+    }
+}
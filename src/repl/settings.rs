@@ -0,0 +1,489 @@
+use crate::utils::output::{OutputError, OutputFormat, format_value, print_warning};
+use datex_core::decompiler::{DecompileOptions, Formatting, decompile_value};
+use datex_core::serde::deserializer::DatexDeserializer;
+use datex_core::values::core_value::CoreValue;
+use datex_core::values::core_values::list::List;
+use datex_core::values::core_values::map::Map;
+use datex_core::values::value::Value;
+use datex_core::values::value_container::ValueContainer;
+use log::LevelFilter;
+use serde::Deserialize;
+use std::fmt::Display;
+use std::path::PathBuf;
+
+/// Depth after which lists and maps are always emptied when a value is truncated,
+/// so that references to a value inside itself do not recurse endlessly
+const MAX_TRUNCATION_DEPTH: usize = 64;
+
+/// Display settings of the REPL, changed with `.set` or the `repl` entry of the config file
+#[derive(Debug, Clone)]
+pub struct ReplSettings {
+    /// colorize DATEX output
+    pub colorized: bool,
+    /// print DATEX output on a single line
+    pub compact: bool,
+    /// number of spaces per indentation level of multiline DATEX output
+    pub indent: usize,
+    /// depth of nested lists and maps after which they are emptied
+    pub max_depth: Option<usize>,
+    /// number of list items and map entries after which they are truncated
+    pub max_length: Option<usize>,
+    /// show runtime logs and the bytecode of each input
+    pub verbose: bool,
+}
+
+impl Default for ReplSettings {
+    fn default() -> Self {
+        ReplSettings {
+            colorized: true,
+            compact: false,
+            indent: 4,
+            max_depth: None,
+            max_length: None,
+            verbose: false,
+        }
+    }
+}
+
+/// `repl` entry of the config file, e.g. `repl: {compact: true, max_length: 10}`
+#[derive(Debug, Default, Deserialize)]
+struct ReplSettingsConfig {
+    colorized: Option<bool>,
+    compact: Option<bool>,
+    indent: Option<usize>,
+    max_depth: Option<usize>,
+    max_length: Option<usize>,
+    verbose: Option<bool>,
+}
+
+impl ReplSettingsConfig {
+    /// Returns the settings of the config entry, validated like the values of `.set`
+    fn settings(self) -> Vec<Result<Setting, String>> {
+        let mut settings = vec![];
+        settings.extend(
+            self.colorized
+                .map(|colorized| Ok(Setting::Colorized(colorized))),
+        );
+        settings.extend(self.compact.map(|compact| Ok(Setting::Compact(compact))));
+        settings.extend(
+            self.indent
+                .map(|indent| Setting::number("indent", Some(indent))),
+        );
+        settings.extend(
+            self.max_depth
+                .map(|max_depth| Setting::number("max_depth", Some(max_depth))),
+        );
+        settings.extend(
+            self.max_length
+                .map(|max_length| Setting::number("max_length", Some(max_length))),
+        );
+        settings.extend(self.verbose.map(|verbose| Ok(Setting::Verbose(verbose))));
+        settings
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct ReplConfig {
+    repl: Option<ReplSettingsConfig>,
+}
+
+/// A single setting changed with `.set <name> <value>`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Setting {
+    Colorized(bool),
+    Compact(bool),
+    Indent(usize),
+    MaxDepth(Option<usize>),
+    MaxLength(Option<usize>),
+    Verbose(bool),
+}
+
+/// Names of all settings, as used by `.set` and the config file
+pub const SETTING_NAMES: [&str; 6] = [
+    "colorized",
+    "compact",
+    "indent",
+    "max_depth",
+    "max_length",
+    "verbose",
+];
+
+impl Setting {
+    /// Parses a setting from its name and value, e.g. `max_depth 3` or `max_depth none`
+    pub fn parse(name: &str, value: &str) -> Result<Setting, String> {
+        let bool_value = || {
+            value
+                .parse::<bool>()
+                .map_err(|_| format!("invalid value '{value}' for {name}, expected true or false"))
+        };
+        Ok(match name {
+            "colorized" => Setting::Colorized(bool_value()?),
+            "compact" => Setting::Compact(bool_value()?),
+            "indent" | "max_depth" | "max_length" => {
+                let number = match value {
+                    "none" => None,
+                    _ => Some(
+                        value
+                            .parse::<usize>()
+                            .map_err(|_| invalid_number(name, value))?,
+                    ),
+                };
+                Setting::number(name, number)?
+            }
+            "verbose" => Setting::Verbose(bool_value()?),
+            _ => {
+                return Err(format!(
+                    "unknown setting '{name}', available settings: {}",
+                    SETTING_NAMES.join(", ")
+                ));
+            }
+        })
+    }
+
+    /// Returns the number setting with the name, `None` removes a limit.
+    /// Numbers must be positive, only `max_depth` and `max_length` can be removed.
+    fn number(name: &str, value: Option<usize>) -> Result<Setting, String> {
+        match (name, value) {
+            (_, Some(0)) => Err(invalid_number(name, "0")),
+            ("indent", Some(indent)) => Ok(Setting::Indent(indent)),
+            ("max_depth", max_depth) => Ok(Setting::MaxDepth(max_depth)),
+            ("max_length", max_length) => Ok(Setting::MaxLength(max_length)),
+            _ => Err(invalid_number(name, "none")),
+        }
+    }
+}
+
+fn invalid_number(name: &str, value: &str) -> String {
+    let expected = match name {
+        "indent" => "a positive number",
+        _ => "a positive number or none",
+    };
+    format!("invalid value '{value}' for {name}, expected {expected}")
+}
+
+impl ReplSettings {
+    /// Returns the initial settings for the output format, overwritten by the `repl` entry
+    /// of the config file and the `--verbose` flag
+    pub fn new(output_format: OutputFormat, config_path: Option<&PathBuf>, verbose: bool) -> Self {
+        let mut settings = ReplSettings {
+            colorized: output_format != OutputFormat::DxCompact,
+            compact: output_format == OutputFormat::DxCompact,
+            ..ReplSettings::default()
+        };
+        let config = config_path
+            .map(|path| -> Result<ReplConfig, String> {
+                let deserializer =
+                    DatexDeserializer::from_dx_file(path.clone()).map_err(|e| format!("{e:?}"))?;
+                ReplConfig::deserialize(deserializer).map_err(|e| format!("{e:?}"))
+            })
+            .transpose();
+        match config {
+            Ok(Some(ReplConfig { repl: Some(config) })) => {
+                for setting in config.settings() {
+                    match setting {
+                        Ok(setting) => settings.set(setting),
+                        Err(e) => print_warning(&format!(
+                            "Ignored a REPL setting of the config file: {e}"
+                        )),
+                    }
+                }
+            }
+            Ok(_) => {}
+            Err(e) => print_warning(&format!(
+                "Could not read the REPL settings from the config file: {e}"
+            )),
+        }
+        settings.verbose |= verbose;
+        settings
+    }
+
+    pub fn set(&mut self, setting: Setting) {
+        match setting {
+            Setting::Colorized(colorized) => self.colorized = colorized,
+            Setting::Compact(compact) => self.compact = compact,
+            Setting::Indent(indent) => self.indent = indent,
+            Setting::MaxDepth(max_depth) => self.max_depth = max_depth,
+            Setting::MaxLength(max_length) => self.max_length = max_length,
+            Setting::Verbose(verbose) => {
+                self.verbose = verbose;
+                self.apply_log_level();
+            }
+        }
+    }
+
+    /// Shows all runtime logs if verbose, otherwise only errors.
+    /// The logger of the runtime must be initialized with all levels, see `init_logger_debug`.
+    pub fn apply_log_level(&self) {
+        log::set_max_level(if self.verbose {
+            LevelFilter::Trace
+        } else {
+            LevelFilter::Error
+        });
+    }
+
+    /// Renders a value in the output format. DATEX output is rendered with the display
+    /// settings and followed by a note if the value was truncated,
+    /// other output formats are not affected.
+    pub fn format_value(
+        &self,
+        value: &ValueContainer,
+        output_format: OutputFormat,
+    ) -> Result<String, OutputError> {
+        if !matches!(output_format, OutputFormat::Dx | OutputFormat::DxCompact) {
+            return format_value(value, output_format);
+        }
+        let options = DecompileOptions {
+            formatting: if self.compact {
+                Formatting::Compact
+            } else {
+                Formatting::Multiline {
+                    indent: self.indent,
+                }
+            },
+            colorized: self.colorized,
+            resolve_slots: true,
+            ..DecompileOptions::default()
+        };
+        if self.max_depth.is_none() && self.max_length.is_none() {
+            return Ok(decompile_value(value, options));
+        }
+        let mut truncation = Truncation::default();
+        let truncated = self.truncate_value(value, 0, &mut truncation);
+        let output = decompile_value(&truncated, options);
+        Ok(match truncation.note() {
+            Some(note) if self.colorized => format!("{output}\n\x1b[2m{note}\x1b[0m"),
+            Some(note) => format!("{output}\n{note}"),
+            None => output,
+        })
+    }
+
+    /// Returns a copy of the value with lists and maps emptied after `max_depth`
+    /// (at most [MAX_TRUNCATION_DEPTH]) and truncated after `max_length`,
+    /// counting the removed parts in `truncation`.
+    /// References are copied as their current values.
+    fn truncate_value(
+        &self,
+        value: &ValueContainer,
+        depth: usize,
+        truncation: &mut Truncation,
+    ) -> ValueContainer {
+        let max_depth = self.max_depth.unwrap_or(MAX_TRUNCATION_DEPTH);
+        let is_emptied = depth >= max_depth.min(MAX_TRUNCATION_DEPTH);
+        let max_length = self.max_length.unwrap_or(usize::MAX);
+        let inner = value.to_value().borrow().inner.clone();
+        match inner {
+            CoreValue::List(list) if is_emptied && !list.is_empty() => {
+                truncation.emptied += 1;
+                ValueContainer::from(Value::from(CoreValue::List(List::default())))
+            }
+            CoreValue::Map(map) if is_emptied && !map.is_empty() => {
+                truncation.emptied += 1;
+                ValueContainer::from(Value::from(CoreValue::Map(Map::default())))
+            }
+            CoreValue::List(list) => {
+                let items = list.as_vec();
+                truncation.hidden += items.len().saturating_sub(max_length);
+                let truncated = items
+                    .iter()
+                    .take(max_length)
+                    .map(|item| self.truncate_value(item, depth + 1, truncation))
+                    .collect::<Vec<_>>();
+                ValueContainer::from(Value::from(CoreValue::List(List::new(truncated))))
+            }
+            CoreValue::Map(map) => {
+                truncation.hidden += map.size().saturating_sub(max_length);
+                let truncated = map
+                    .into_iter()
+                    .take(max_length)
+                    .map(|(key, value)| {
+                        (
+                            ValueContainer::from(key),
+                            self.truncate_value(&value, depth + 1, truncation),
+                        )
+                    })
+                    .collect::<Vec<_>>();
+                ValueContainer::from(Value::from(CoreValue::Map(Map::from(truncated))))
+            }
+            _ => value.clone(),
+        }
+    }
+}
+
+/// Parts of a value removed by [ReplSettings::truncate_value]
+#[derive(Debug, Default)]
+struct Truncation {
+    /// list items and map entries after `max_length`
+    hidden: usize,
+    /// non-empty lists and maps after `max_depth`
+    emptied: usize,
+}
+
+impl Truncation {
+    /// Returns a note about the removed parts, or `None` if nothing was removed
+    fn note(&self) -> Option<String> {
+        let plural = |count: usize, singular: &str, plural: &str| match count {
+            1 => format!("1 {singular}"),
+            _ => format!("{count} {plural}"),
+        };
+        let mut parts = vec![];
+        if self.hidden > 0 {
+            parts.push(plural(self.hidden, "entry", "entries") + " hidden");
+        }
+        if self.emptied > 0 {
+            parts.push(
+                plural(self.emptied, "nested list or map", "nested lists or maps") + " emptied",
+            );
+        }
+        (!parts.is_empty()).then(|| format!("… {}", parts.join(", ")))
+    }
+}
+
+impl Display for ReplSettings {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let limit = |limit: Option<usize>| match limit {
+            Some(limit) => limit.to_string(),
+            None => "none".to_string(),
+        };
+        writeln!(f, "colorized   {}", self.colorized)?;
+        writeln!(f, "compact     {}", self.compact)?;
+        writeln!(f, "indent      {}", self.indent)?;
+        writeln!(f, "max_depth   {}", limit(self.max_depth))?;
+        writeln!(f, "max_length  {}", limit(self.max_length))?;
+        write!(f, "verbose     {}", self.verbose)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use datex_core::values::core_values::integer::Integer;
+
+    fn list(items: Vec<ValueContainer>) -> ValueContainer {
+        ValueContainer::from(Value::from(CoreValue::List(List::new(items))))
+    }
+
+    fn integers(integers: &[i32]) -> ValueContainer {
+        list(
+            integers
+                .iter()
+                .map(|i| ValueContainer::from(Integer::from(*i)))
+                .collect(),
+        )
+    }
+
+    fn plain_settings() -> ReplSettings {
+        ReplSettings {
+            colorized: false,
+            compact: true,
+            ..ReplSettings::default()
+        }
+    }
+
+    #[test]
+    fn parses_settings() {
+        assert!(matches!(
+            Setting::parse("compact", "true"),
+            Ok(Setting::Compact(true))
+        ));
+        assert!(matches!(
+            Setting::parse("indent", "2"),
+            Ok(Setting::Indent(2))
+        ));
+        assert!(matches!(
+            Setting::parse("max_depth", "none"),
+            Ok(Setting::MaxDepth(None))
+        ));
+        assert!(matches!(
+            Setting::parse("max_length", "10"),
+            Ok(Setting::MaxLength(Some(10)))
+        ));
+        assert!(Setting::parse("compact", "yes").is_err());
+        assert!(Setting::parse("indent", "none").is_err());
+        assert!(Setting::parse("max_length", "-1").is_err());
+        assert!(Setting::parse("unknown", "true").is_err());
+    }
+
+    #[test]
+    fn rejects_zero_in_set_and_config() {
+        for name in ["indent", "max_depth", "max_length"] {
+            let error = Setting::parse(name, "0").unwrap_err();
+            let config = ReplSettingsConfig {
+                indent: Some(0),
+                max_depth: Some(0),
+                max_length: Some(0),
+                ..ReplSettingsConfig::default()
+            };
+            assert!(config.settings().contains(&Err(error)));
+        }
+    }
+
+    #[test]
+    fn ignores_invalid_config_settings() {
+        let path = std::env::temp_dir().join(format!("datex-repl-{}.dx", std::process::id()));
+        std::fs::write(&path, "{repl: {compact: true, max_length: 0}}").unwrap();
+        let settings = ReplSettings::new(OutputFormat::Dx, Some(&path), false);
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(settings.compact);
+        assert_eq!(settings.max_length, None);
+    }
+
+    #[test]
+    fn truncates_long_lists() {
+        let settings = ReplSettings {
+            max_length: Some(2),
+            ..plain_settings()
+        };
+        let value = settings
+            .format_value(&integers(&[1, 2, 3, 4]), OutputFormat::Dx)
+            .unwrap();
+        assert_eq!(value, "[1,2]\n… 2 entries hidden");
+
+        let value = settings
+            .format_value(&integers(&[1, 2]), OutputFormat::Dx)
+            .unwrap();
+        assert_eq!(value, "[1,2]");
+    }
+
+    #[test]
+    fn empties_deeply_nested_values() {
+        let settings = ReplSettings {
+            max_depth: Some(1),
+            ..plain_settings()
+        };
+        let value = list(vec![integers(&[1]), integers(&[]), integers(&[2, 3])]);
+        assert_eq!(
+            settings.format_value(&value, OutputFormat::Dx).unwrap(),
+            "[[],[],[]]\n… 2 nested lists or maps emptied"
+        );
+    }
+
+    #[test]
+    fn limits_the_truncation_depth() {
+        let settings = ReplSettings {
+            max_length: Some(10),
+            ..plain_settings()
+        };
+        let mut value = integers(&[1]);
+        for _ in 0..MAX_TRUNCATION_DEPTH {
+            value = list(vec![value]);
+        }
+        let output = settings.format_value(&value, OutputFormat::Dx).unwrap();
+        // the innermost list is at MAX_TRUNCATION_DEPTH and emptied
+        let nested = "[".repeat(MAX_TRUNCATION_DEPTH + 1) + &"]".repeat(MAX_TRUNCATION_DEPTH + 1);
+        assert_eq!(output, format!("{nested}\n… 1 nested list or map emptied"));
+    }
+
+    #[test]
+    fn indents_multiline_output() {
+        let settings = ReplSettings {
+            compact: false,
+            indent: 2,
+            ..plain_settings()
+        };
+        // lists longer than 60 characters are split into lines
+        let value = integers(&(10..50).collect::<Vec<_>>());
+        let output = settings.format_value(&value, OutputFormat::Dx).unwrap();
+        assert!(output.starts_with("[\n  10,\n  11,\n"), "{output}");
+    }
+}
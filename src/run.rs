@@ -0,0 +1,364 @@
+use crate::utils::config::{ConfigError, create_runtime_with_config};
+use crate::utils::errors::{
+    CommandError, EXIT_CODE_COMPILER_ERROR, EXIT_CODE_ERROR, EXIT_CODE_EXECUTION_ERROR,
+};
+use crate::utils::execution::{EvaluationError, count_placeholders, execute_and_format};
+use crate::utils::output::{OutputError, OutputFormat, print_error};
+use datex_core::compiler::extract_static_value_from_script;
+use datex_core::crypto::crypto_native::CryptoNative;
+use datex_core::run_async;
+use datex_core::runtime::Runtime;
+use datex_core::runtime::execution_context::{ExecutionContext, ScriptExecutionError};
+use datex_core::runtime::global_context::{GlobalContext, set_global_context};
+use datex_core::utils::time_native::TimeNative;
+use datex_core::values::value_container::ValueContainer;
+use std::fmt::Display;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+use tokio::time::sleep;
+
+/// The source of a DATEX script executed by `datex run`
+#[derive(Debug, Clone)]
+pub enum ScriptSource {
+    File(PathBuf),
+    Stdin,
+    Expression(String),
+}
+
+impl ScriptSource {
+    /// Reads the script text from the source
+    pub fn read(&self) -> Result<String, std::io::Error> {
+        match self {
+            ScriptSource::File(path) => fs::read_to_string(path),
+            ScriptSource::Stdin => std::io::read_to_string(std::io::stdin()),
+            ScriptSource::Expression(expression) => Ok(expression.clone()),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RunOptions {
+    pub source: ScriptSource,
+    /// positional arguments, inserted for the `?` placeholders in the script
+    pub args: Vec<String>,
+    /// named arguments in the form `name=value`, declared as constants before the script runs
+    pub named_args: Vec<String>,
+    /// re-execute the script whenever the file changes
+    pub watch: bool,
+    pub output_format: OutputFormat,
+    pub verbose: bool,
+    pub config_path: Option<PathBuf>,
+}
+
+/// Interval in which a watched file is checked for modifications
+const WATCH_INTERVAL: Duration = Duration::from_millis(200);
+
+#[derive(Debug)]
+pub enum RunError {
+    IOError(std::io::Error),
+    ConfigError(ConfigError),
+    InvalidArgument(String),
+    ScriptExecutionError(ScriptExecutionError),
+    OutputError(OutputError),
+}
+
+impl CommandError for RunError {
+    fn exit_code(&self) -> i32 {
+        match self {
+            RunError::ScriptExecutionError(ScriptExecutionError::CompilerError(_)) => {
+                EXIT_CODE_COMPILER_ERROR
+            }
+            RunError::ScriptExecutionError(ScriptExecutionError::ExecutionError(_)) => {
+                EXIT_CODE_EXECUTION_ERROR
+            }
+            _ => EXIT_CODE_ERROR,
+        }
+    }
+}
+
+impl Display for RunError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RunError::IOError(e) => write!(f, "[IO Error] {e}"),
+            RunError::ConfigError(e) => write!(f, "[Config Error] {e}"),
+            RunError::InvalidArgument(e) => write!(f, "[Invalid Argument] {e}"),
+            RunError::ScriptExecutionError(ScriptExecutionError::CompilerError(e)) => {
+                write!(f, "[Compiler Error] {e}")
+            }
+            RunError::ScriptExecutionError(ScriptExecutionError::ExecutionError(e)) => {
+                write!(f, "[Execution Error] {e}")
+            }
+            RunError::OutputError(e) => write!(f, "[Output Error] {e}"),
+        }
+    }
+}
+
+impl From<std::io::Error> for RunError {
+    fn from(err: std::io::Error) -> Self {
+        RunError::IOError(err)
+    }
+}
+impl From<ConfigError> for RunError {
+    fn from(err: ConfigError) -> Self {
+        RunError::ConfigError(err)
+    }
+}
+impl From<ScriptExecutionError> for RunError {
+    fn from(err: ScriptExecutionError) -> Self {
+        RunError::ScriptExecutionError(err)
+    }
+}
+impl From<EvaluationError> for RunError {
+    fn from(err: EvaluationError) -> Self {
+        match err {
+            EvaluationError::ScriptExecutionError(e) => RunError::ScriptExecutionError(e),
+            EvaluationError::OutputError(e) => RunError::OutputError(e),
+        }
+    }
+}
+
+/// Characters that only occur in structured DATEX values, not in plain words or paths
+const STRUCTURE_CHARS: [char; 7] = ['{', '}', '[', ']', '(', ')', '"'];
+
+/// Parses a command line argument as a static DATEX value.
+/// Words that are not a valid static value (e.g. `hello` after shell quoting)
+/// are passed as text, invalid structured values (e.g. `{a: 1`) are an error.
+fn parse_argument(argument: &str) -> Result<ValueContainer, RunError> {
+    let error = match extract_static_value_from_script(argument) {
+        Ok(Some(value)) => return Ok(value),
+        Ok(None) => "only static values can be passed".to_string(),
+        Err(e) => e.to_string().trim_end().to_string(),
+    };
+    if argument.contains(STRUCTURE_CHARS) {
+        Err(RunError::InvalidArgument(format!(
+            "'{argument}' is not a valid DATEX value: {error}"
+        )))
+    } else {
+        Ok(ValueContainer::from(argument))
+    }
+}
+
+/// Parses a named command line argument in the form `name=value`
+fn parse_named_argument(argument: &str) -> Result<(String, ValueContainer), RunError> {
+    let (name, value) = argument.split_once('=').ok_or_else(|| {
+        RunError::InvalidArgument(format!("'{argument}' must have the form name=value"))
+    })?;
+    let is_identifier = name
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if !is_identifier {
+        return Err(RunError::InvalidArgument(format!(
+            "'{name}' is not a valid variable name"
+        )));
+    }
+    Ok((name.to_string(), parse_argument(value)?))
+}
+
+/// Parsed positional and named script arguments
+struct ScriptArguments {
+    args: Vec<ValueContainer>,
+    arg_names: Vec<String>,
+    named_args: Vec<ValueContainer>,
+}
+
+impl ScriptArguments {
+    fn parse(options: &RunOptions) -> Result<Self, RunError> {
+        let args = options
+            .args
+            .iter()
+            .map(|arg| parse_argument(arg))
+            .collect::<Result<_, _>>()?;
+        let (arg_names, named_args) = options
+            .named_args
+            .iter()
+            .map(|arg| parse_named_argument(arg))
+            .collect::<Result<Vec<_>, _>>()?
+            .into_iter()
+            .unzip();
+        Ok(ScriptArguments {
+            args,
+            arg_names,
+            named_args,
+        })
+    }
+}
+
+/// Loads a DATEX script from a file, stdin or an inline expression, executes it
+/// in a fresh execution context and prints the decompiled result.
+/// In watch mode, the script is executed again every time the file changes.
+pub async fn run(options: RunOptions) -> Result<(), RunError> {
+    if options.watch && !matches!(options.source, ScriptSource::File(_)) {
+        return Err(RunError::InvalidArgument(
+            "--watch can only be used with a file".to_string(),
+        ));
+    }
+    let arguments = ScriptArguments::parse(&options)?;
+
+    set_global_context(GlobalContext::new(
+        Arc::new(CryptoNative),
+        Arc::new(TimeNative),
+    ));
+
+    run_async! {
+        let runtime = create_runtime_with_config(options.config_path.clone(), options.verbose).await?;
+
+        match (&options.source, options.watch) {
+            (ScriptSource::File(path), true) => watch(&runtime, path, &options, &arguments).await,
+            (source, _) => {
+                let script = source.read()?;
+                execute_script(&runtime, &script, &options, &arguments).await
+            }
+        }
+    }
+}
+
+/// Modification time and size of a file, used to detect changes of a watched file
+fn file_state(path: &PathBuf) -> Option<(Option<SystemTime>, u64)> {
+    fs::metadata(path)
+        .ok()
+        .map(|metadata| (metadata.modified().ok(), metadata.len()))
+}
+
+/// Executes the script every time the file is modified, until the process is terminated.
+/// A change is only picked up once the file has not changed for one interval,
+/// so a file that is still being written is not executed.
+/// Only the script file is watched, imported files need to be added once DATEX supports imports.
+async fn watch(
+    runtime: &Runtime,
+    path: &PathBuf,
+    options: &RunOptions,
+    arguments: &ScriptArguments,
+) -> Result<(), RunError> {
+    let mut executed_state = None;
+    let mut previous_state = None;
+    loop {
+        let state = file_state(path);
+        let is_stable = state == previous_state;
+        previous_state = state;
+        if state.is_some() && is_stable && state != executed_state {
+            executed_state = state;
+
+            // clear the screen and move the cursor to the top left
+            print!("\x1b[2J\x1b[H");
+            println!("\x1b[2m[Watching {}]\x1b[0m\n", path.display());
+
+            let result = match fs::read_to_string(path) {
+                Ok(script) => execute_script(runtime, &script, options, arguments).await,
+                Err(err) => Err(RunError::from(err)),
+            };
+            if let Err(err) = result {
+                print_error(&err);
+            }
+        }
+        sleep(WATCH_INTERVAL).await;
+    }
+}
+
+/// Executes a script in a fresh execution context and prints the decompiled result
+async fn execute_script(
+    runtime: &Runtime,
+    script: &str,
+    options: &RunOptions,
+    arguments: &ScriptArguments,
+) -> Result<(), RunError> {
+    let placeholder_count = count_placeholders(script);
+    if placeholder_count != arguments.args.len() {
+        return Err(RunError::InvalidArgument(format!(
+            "the script expects {placeholder_count} argument(s), but {} were provided",
+            arguments.args.len()
+        )));
+    }
+
+    let mut execution_context = if options.verbose {
+        ExecutionContext::local_debug(false)
+    } else {
+        ExecutionContext::local()
+    };
+
+    // declare the named arguments as constants in the execution context
+    if !arguments.arg_names.is_empty() {
+        let declarations = arguments
+            .arg_names
+            .iter()
+            .map(|name| format!("const {name} = ?;"))
+            .collect::<String>();
+        runtime
+            .execute(
+                &declarations,
+                &arguments.named_args,
+                Some(&mut execution_context),
+            )
+            .await?;
+    }
+
+    let result = execute_and_format(
+        runtime,
+        script,
+        &arguments.args,
+        &mut execution_context,
+        options.output_format,
+    )
+    .await?;
+
+    if let Some(result) = result {
+        println!("{result}");
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use datex_core::values::core_values::integer::Integer;
+
+    #[test]
+    fn parses_arguments_as_datex_values() {
+        assert_eq!(
+            parse_argument("42").unwrap(),
+            ValueContainer::from(Integer::from(42))
+        );
+        assert_eq!(
+            parse_argument("\"42\"").unwrap(),
+            ValueContainer::from("42")
+        );
+        assert_eq!(parse_argument("true").unwrap(), ValueContainer::from(true));
+    }
+
+    #[test]
+    fn passes_words_as_text() {
+        assert_eq!(
+            parse_argument("hello").unwrap(),
+            ValueContainer::from("hello")
+        );
+        assert_eq!(
+            parse_argument("hello world").unwrap(),
+            ValueContainer::from("hello world")
+        );
+        assert_eq!(
+            parse_argument("./path/to/file.txt").unwrap(),
+            ValueContainer::from("./path/to/file.txt")
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_structured_values() {
+        assert!(parse_argument("{a: 1").is_err());
+        assert!(parse_argument("[1, 2").is_err());
+        assert!(parse_argument("\"unterminated").is_err());
+        assert!(parse_named_argument("x={a: 1").is_err());
+    }
+
+    #[test]
+    fn parses_named_arguments() {
+        let (name, value) = parse_named_argument("x=1").unwrap();
+        assert_eq!(name, "x");
+        assert_eq!(value, ValueContainer::from(Integer::from(1)));
+        assert!(parse_named_argument("x").is_err());
+        assert!(parse_named_argument("1x=1").is_err());
+    }
+}
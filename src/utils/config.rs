@@ -1,4 +1,4 @@
-use datex_core::decompiler::{DecompileOptions, Formatting, decompile_value};
+use crate::utils::output::canonical_source;
 use datex_core::network::com_interfaces::default_com_interfaces::websocket::websocket_common::WebSocketClientInterfaceSetupData;
 use datex_core::runtime::{Runtime, RuntimeConfig};
 use datex_core::serde::deserializer::DatexDeserializer;
@@ -6,16 +6,28 @@ use datex_core::serde::error::{DeserializationError, SerializationError};
 use datex_core::serde::serializer::to_value_container;
 use datex_core::values::core_values::endpoint::Endpoint;
 use serde::Deserialize;
+use std::fmt::Display;
 use std::fs;
 use std::path::PathBuf;
 
 #[derive(Debug)]
+#[allow(clippy::enum_variant_names)]
 pub enum ConfigError {
     SerializationError(SerializationError),
     DeserializationError(DeserializationError),
     IOError(std::io::Error),
 }
 
+impl Display for ConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConfigError::SerializationError(e) => write!(f, "{e}"),
+            ConfigError::DeserializationError(e) => write!(f, "{e}"),
+            ConfigError::IOError(e) => write!(f, "{e}"),
+        }
+    }
+}
+
 impl From<SerializationError> for ConfigError {
     fn from(err: SerializationError) -> Self {
         ConfigError::SerializationError(err)
@@ -84,13 +96,7 @@ pub fn create_new_config_file(
     config_path.push(".datex");
     config_path.push(format!("{endpoint}.dx"));
     let config = to_value_container(&config)?;
-    let datex_script = decompile_value(
-        &config,
-        DecompileOptions {
-            formatting: Formatting::multiline(),
-            ..DecompileOptions::default()
-        },
-    );
+    let datex_script = canonical_source(&config);
     fs::write(config_path.clone(), datex_script)?;
 
     println!("Created new config file for {endpoint} at {config_path:?}");
@@ -98,9 +104,15 @@ pub fn create_new_config_file(
     Ok(config_path)
 }
 
-pub fn get_config(custom_config_path: Option<PathBuf>) -> Result<RuntimeConfig, ConfigError> {
+/// Returns the path of the config file, which is either the custom config path or the first
+/// config file in the home directory `.datex` folder. If there is no config file yet,
+/// a new config file for a random endpoint is created.
+/// Returns `None` if there is no home directory.
+pub fn get_config_path(
+    custom_config_path: Option<PathBuf>,
+) -> Result<Option<PathBuf>, ConfigError> {
     Ok(match custom_config_path {
-        Some(path) => read_config_file(path)?,
+        Some(path) => Some(path),
         None => {
             match home::home_dir() {
                 Some(path) if !path.as_os_str().is_empty() => {
@@ -109,23 +121,62 @@ pub fn get_config(custom_config_path: Option<PathBuf>) -> Result<RuntimeConfig,
                     // if no files yet, create a new config file for a random endpoint
                     if dx_files.is_empty() {
                         let endpoint = Endpoint::random();
-                        let config_path = create_new_config_file(path.clone(), endpoint)?;
-                        read_config_file(config_path)?
+                        Some(create_new_config_file(path.clone(), endpoint)?)
                     } else {
-                        // if there are files, read the first one
-                        let config_path = dx_files.first().unwrap().clone();
-                        read_config_file(config_path)?
+                        // if there are files, use the first one
+                        Some(dx_files.first().unwrap().clone())
                     }
                 }
-                _ => {
-                    eprintln!("Unable to get home directory, using temporary endpoint.");
-                    RuntimeConfig::new_with_endpoint(Endpoint::random())
-                }
+                _ => None,
             }
         }
     })
 }
 
+pub fn get_config(custom_config_path: Option<PathBuf>) -> Result<RuntimeConfig, ConfigError> {
+    Ok(match get_config_path(custom_config_path)? {
+        Some(config_path) => read_config_file(config_path)?,
+        None => {
+            eprintln!("Unable to get home directory, using temporary endpoint.");
+            RuntimeConfig::new_with_endpoint(Endpoint::random())
+        }
+    })
+}
+
+/// Returns the file name of the REPL history for an endpoint.
+/// Characters that are not safe in file names, such as the `/` of an endpoint
+/// instance or `*`, are percent-encoded, e.g. `@ben/42` -> `@ben%2F42.txt`.
+fn history_file_name(endpoint: &Endpoint) -> String {
+    let mut name = String::new();
+    for byte in endpoint.to_string().bytes() {
+        if byte.is_ascii_alphanumeric() || b"@+-_".contains(&byte) {
+            name.push(byte as char);
+        } else {
+            name.push_str(&format!("%{byte:02X}"));
+        }
+    }
+    name.push_str(".txt");
+    name
+}
+
+/// Returns the path of the REPL history file for the endpoint in the home directory
+/// `.datex/history` folder, or `None` if there is no home directory.
+/// The history folder is created if it doesn't exist.
+pub fn get_history_path(endpoint: &Endpoint) -> Result<Option<PathBuf>, ConfigError> {
+    match home::home_dir() {
+        Some(path) if !path.as_os_str().is_empty() => {
+            let mut history_dir = path;
+            history_dir.push(".datex");
+            history_dir.push("history");
+            if !history_dir.exists() {
+                fs::create_dir_all(&history_dir)?;
+            }
+            Ok(Some(history_dir.join(history_file_name(endpoint))))
+        }
+        _ => Ok(None),
+    }
+}
+
 pub async fn create_runtime_with_config(
     custom_config_path: Option<PathBuf>,
     force_debug: bool,
@@ -136,15 +187,27 @@ pub async fn create_runtime_with_config(
         config.debug = Some(true);
     }
     let runtime = Runtime::create_native(config).await;
+    Ok(runtime)
+}
 
-    let cli_version = env!("CARGO_PKG_VERSION");
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
 
-    println!("================================================");
-    println!("DATEX REPL v{cli_version}");
-    println!("DATEX Core version: {}", runtime.version);
-    println!("Endpoint: {}", runtime.endpoint());
-    println!("\nexit using [CTRL + C]");
-    println!("================================================\n");
+    fn file_name(endpoint: &str) -> String {
+        history_file_name(&Endpoint::from_str(endpoint).unwrap())
+    }
 
-    Ok(runtime)
+    #[test]
+    fn keeps_plain_endpoints_as_history_file_names() {
+        assert_eq!(file_name("@jonas"), "@jonas.txt");
+        assert_eq!(file_name("@+unyt"), "@+unyt.txt");
+    }
+
+    #[test]
+    fn encodes_endpoint_instances_in_history_file_names() {
+        assert_eq!(file_name("@ben/42"), "@ben%2F42.txt");
+        assert_eq!(file_name("@ben/*"), "@ben%2F%2A.txt");
+    }
 }
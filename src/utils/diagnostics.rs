@@ -0,0 +1,100 @@
+use datex_core::compiler::error::CompilerError;
+use serde::Serialize;
+use std::fmt::Display;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+/// A 1-based line and column in a source file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Position {
+    pub line: usize,
+    pub column: usize,
+}
+
+/// A compiler error or warning located in a source file
+#[derive(Debug, Clone, Serialize)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub path: PathBuf,
+    /// source position, if the diagnostic has one
+    pub position: Option<Position>,
+    pub message: String,
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.position {
+            Some(Position { line, column }) => {
+                write!(
+                    f,
+                    "{}:{line}:{column}: {}",
+                    self.path.display(),
+                    self.message
+                )
+            }
+            None => write!(f, "{}: {}", self.path.display(), self.message),
+        }
+    }
+}
+
+/// Converts a compiler error into diagnostics with source positions.
+/// Parse errors contain a span and result in one diagnostic per error,
+/// all other errors result in a single diagnostic without position.
+pub fn compiler_error_diagnostics(
+    error: &CompilerError,
+    path: &Path,
+    source: &str,
+) -> Vec<Diagnostic> {
+    match error {
+        CompilerError::ParseErrors(errors) => {
+            // the parser strips a shebang line before computing spans
+            let offset = if source.starts_with("#!") {
+                source.find('\n').map(|pos| pos + 1).unwrap_or(source.len())
+            } else {
+                0
+            };
+            errors
+                .iter()
+                .map(|e| Diagnostic {
+                    severity: Severity::Error,
+                    path: path.to_path_buf(),
+                    position: e
+                        .span()
+                        .map(|span| line_and_column(source, span.start + offset)),
+                    message: e.message(),
+                })
+                .collect()
+        }
+        e => vec![Diagnostic {
+            severity: Severity::Error,
+            path: path.to_path_buf(),
+            position: None,
+            message: e.to_string(),
+        }],
+    }
+}
+
+/// Formats compiler error diagnostics with one `[Compiler Error]` line per diagnostic
+pub fn format_compiler_errors(diagnostics: &[Diagnostic]) -> String {
+    diagnostics
+        .iter()
+        .map(|d| format!("[Compiler Error] {d}"))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Returns the 1-based line and column of a byte offset in the source
+pub fn line_and_column(source: &str, offset: usize) -> Position {
+    let offset = offset.min(source.len());
+    let before = &source[..offset];
+    let line = before.matches('\n').count() + 1;
+    let line_start = before.rfind('\n').map(|pos| pos + 1).unwrap_or(0);
+    let column = before[line_start..].chars().count() + 1;
+    Position { line, column }
+}
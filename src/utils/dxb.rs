@@ -0,0 +1,167 @@
+use crate::utils::errors::{CommandError, EXIT_CODE_ERROR};
+use datex_core::global::dxb_block::DXBBlock;
+use std::fmt::Display;
+use std::fs;
+use std::path::Path;
+
+#[derive(Debug)]
+pub enum DecompileError {
+    IOError(std::io::Error),
+    InvalidHex(String),
+    InvalidBlock(String),
+    DecompilerError(String),
+}
+
+impl CommandError for DecompileError {
+    fn exit_code(&self) -> i32 {
+        EXIT_CODE_ERROR
+    }
+}
+
+impl Display for DecompileError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DecompileError::IOError(e) => write!(f, "[IO Error] {e}"),
+            DecompileError::InvalidHex(e) => write!(f, "[Invalid Hex] {e}"),
+            DecompileError::InvalidBlock(e) => write!(f, "[Invalid Block] {e}"),
+            DecompileError::DecompilerError(e) => write!(f, "[Decompiler Error] {e}"),
+        }
+    }
+}
+
+impl From<std::io::Error> for DecompileError {
+    fn from(err: std::io::Error) -> Self {
+        DecompileError::IOError(err)
+    }
+}
+
+/// Parses a hex string into bytes.
+/// Whitespace, commas and an optional `0x` prefix are ignored.
+fn parse_hex(hex: &str) -> Result<Vec<u8>, DecompileError> {
+    let hex = hex.trim();
+    let hex = hex.strip_prefix("0x").unwrap_or(hex);
+    let digits = hex
+        .chars()
+        .filter(|c| !c.is_whitespace() && *c != ',')
+        .collect::<Vec<_>>();
+    if let Some(c) = digits.iter().find(|c| !c.is_ascii_hexdigit()) {
+        return Err(DecompileError::InvalidHex(format!(
+            "'{c}' is not a hex digit"
+        )));
+    }
+    if digits.len() % 2 != 0 {
+        return Err(DecompileError::InvalidHex(
+            "odd number of hex digits".to_string(),
+        ));
+    }
+    digits
+        .chunks(2)
+        .map(|pair| {
+            let pair = pair.iter().collect::<String>();
+            u8::from_str_radix(&pair, 16)
+                .map_err(|_| DecompileError::InvalidHex(format!("'{pair}' is not a hex byte")))
+        })
+        .collect()
+}
+
+/// Returns the body of a full DXB block (including routing and block headers),
+/// or the bytes unchanged if they do not start with the DXB magic number
+fn block_body(dxb: Vec<u8>) -> Result<Vec<u8>, DecompileError> {
+    if DXBBlock::has_dxb_magic_number(&dxb) {
+        let block =
+            DXBBlock::from_bytes(&dxb).map_err(|e| DecompileError::InvalidBlock(e.to_string()))?;
+        Ok(block.body)
+    } else {
+        Ok(dxb)
+    }
+}
+
+/// Reads DXB from a file, or a hex string from stdin if the path is `-`.
+/// If the bytes are a full DXB block (including routing and block headers),
+/// only the body of the block is returned.
+pub fn read_dxb_body(path: &Path) -> Result<Vec<u8>, DecompileError> {
+    let dxb = if path.as_os_str() == "-" {
+        parse_hex(&std::io::read_to_string(std::io::stdin())?)?
+    } else {
+        fs::read(path)?
+    };
+    block_body(dxb)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use datex_core::global::protocol_structures::block_header::BlockHeader;
+    use datex_core::global::protocol_structures::encrypted_header::EncryptedHeader;
+    use datex_core::global::protocol_structures::routing_header::RoutingHeader;
+
+    #[test]
+    fn parses_hex_with_separators_and_prefix() {
+        assert_eq!(parse_hex("01ff").unwrap(), vec![0x01, 0xff]);
+        assert_eq!(parse_hex("0x01 02,\n03\n").unwrap(), vec![1, 2, 3]);
+        assert_eq!(parse_hex("  0xAbCd ").unwrap(), vec![0xab, 0xcd]);
+        assert_eq!(parse_hex("").unwrap(), Vec::<u8>::new());
+    }
+
+    #[test]
+    fn rejects_odd_length_hex() {
+        assert!(matches!(
+            parse_hex("012"),
+            Err(DecompileError::InvalidHex(_))
+        ));
+        assert!(matches!(
+            parse_hex("0x1"),
+            Err(DecompileError::InvalidHex(_))
+        ));
+    }
+
+    #[test]
+    fn rejects_invalid_hex_digits() {
+        assert!(matches!(
+            parse_hex("0g"),
+            Err(DecompileError::InvalidHex(_))
+        ));
+        assert!(matches!(
+            parse_hex("+f"),
+            Err(DecompileError::InvalidHex(_))
+        ));
+        assert!(matches!(
+            parse_hex("0x0x01"),
+            Err(DecompileError::InvalidHex(_))
+        ));
+    }
+
+    #[test]
+    fn returns_the_body_of_full_blocks() {
+        let block = DXBBlock::new(
+            RoutingHeader::default(),
+            BlockHeader::default(),
+            EncryptedHeader::default(),
+            vec![1, 2, 3],
+        );
+        assert_eq!(
+            block_body(block.to_bytes().unwrap()).unwrap(),
+            vec![1, 2, 3]
+        );
+        // bytes without the magic number are a plain body
+        assert_eq!(block_body(vec![1, 2, 3]).unwrap(), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn rejects_truncated_block_headers() {
+        let block = DXBBlock::new(
+            RoutingHeader::default(),
+            BlockHeader::default(),
+            EncryptedHeader::default(),
+            vec![],
+        )
+        .to_bytes()
+        .unwrap();
+        for length in [2, 4, block.len() - 1] {
+            assert!(matches!(
+                block_body(block[..length].to_vec()),
+                Err(DecompileError::InvalidBlock(_))
+            ));
+        }
+    }
+}
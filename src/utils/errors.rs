@@ -0,0 +1,23 @@
+use crate::utils::output::print_error;
+use std::fmt::Display;
+
+/// Exit code for errors that occur before a script is executed (I/O, config, invalid input)
+pub const EXIT_CODE_ERROR: i32 = 1;
+/// Exit code if a script could not be compiled
+pub const EXIT_CODE_COMPILER_ERROR: i32 = 2;
+/// Exit code if a script failed during execution
+pub const EXIT_CODE_EXECUTION_ERROR: i32 = 3;
+
+/// An error returned by a subcommand
+pub trait CommandError: Display {
+    /// Returns the process exit code for this error
+    fn exit_code(&self) -> i32;
+}
+
+/// Prints the error of a failed subcommand to stderr and exits with its exit code
+pub fn exit_on_error(result: Result<(), impl CommandError>) {
+    if let Err(err) = result {
+        print_error(&err);
+        std::process::exit(err.exit_code());
+    }
+}
@@ -0,0 +1,50 @@
+use crate::utils::output::{OutputError, OutputFormat, format_value};
+use datex_core::ast::lexer::Token;
+use datex_core::runtime::Runtime;
+use datex_core::runtime::execution_context::{ExecutionContext, ScriptExecutionError};
+use datex_core::values::value_container::ValueContainer;
+use logos::Logos;
+
+#[derive(Debug)]
+pub enum EvaluationError {
+    ScriptExecutionError(ScriptExecutionError),
+    OutputError(OutputError),
+}
+
+impl From<ScriptExecutionError> for EvaluationError {
+    fn from(err: ScriptExecutionError) -> Self {
+        EvaluationError::ScriptExecutionError(err)
+    }
+}
+impl From<OutputError> for EvaluationError {
+    fn from(err: OutputError) -> Self {
+        EvaluationError::OutputError(err)
+    }
+}
+
+/// Executes a DATEX script in the given execution context and returns the
+/// result value rendered in the given output format, if the script returned a value.
+/// Used by `datex run`. The REPL executes scripts itself to keep the result values and
+/// renders them with its display settings, falling back to [format_value] for JSON and DXB.
+pub async fn execute_and_format(
+    runtime: &Runtime,
+    script: &str,
+    inserted_values: &[ValueContainer],
+    execution_context: &mut ExecutionContext,
+    output_format: OutputFormat,
+) -> Result<Option<String>, EvaluationError> {
+    let result = runtime
+        .execute(script, inserted_values, Some(execution_context))
+        .await?;
+    Ok(result
+        .map(|result| format_value(&result, output_format))
+        .transpose()?)
+}
+
+/// Returns the number of `?` placeholders in a DATEX script that must be
+/// filled with inserted values.
+pub fn count_placeholders(script: &str) -> usize {
+    Token::lexer(script)
+        .filter(|token| matches!(token, Ok(Token::Placeholder)))
+        .count()
+}
@@ -0,0 +1,35 @@
+use std::fs;
+use std::path::PathBuf;
+
+/// Collects all `.dx` files from the given paths.
+/// Files are returned as they are, directories are searched recursively,
+/// skipping hidden entries.
+pub fn collect_dx_files(paths: &[PathBuf]) -> Result<Vec<PathBuf>, std::io::Error> {
+    let mut dx_files = vec![];
+    for path in paths {
+        if path.is_dir() {
+            let mut entries = fs::read_dir(path)?
+                .map(|entry| entry.map(|e| e.path()))
+                .collect::<Result<Vec<_>, _>>()?;
+            entries.sort();
+            for entry in entries {
+                // skip hidden files and directories such as .git
+                let is_hidden = entry
+                    .file_name()
+                    .and_then(|name| name.to_str())
+                    .is_some_and(|name| name.starts_with('.'));
+                if is_hidden {
+                    continue;
+                }
+                if entry.is_dir() {
+                    dx_files.extend(collect_dx_files(&[entry])?);
+                } else if entry.extension().and_then(|ext| ext.to_str()) == Some("dx") {
+                    dx_files.push(entry);
+                }
+            }
+        } else {
+            dx_files.push(path.clone());
+        }
+    }
+    Ok(dx_files)
+}
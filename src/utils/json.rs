@@ -0,0 +1,70 @@
+use datex_core::values::core_value::CoreValue;
+use datex_core::values::core_values::decimal::Decimal;
+use datex_core::values::core_values::decimal::typed_decimal::TypedDecimal;
+use datex_core::values::core_values::integer::Integer;
+use datex_core::values::core_values::list::List;
+use datex_core::values::core_values::map::Map;
+use datex_core::values::value::Value;
+use datex_core::values::value_container::ValueContainer;
+
+/// Converts a JSON value into the DATEX value with the same literal representation,
+/// e.g. `1` into an integer and `null` into null.
+/// The serde serializer of datex-core is not used here, since it maps JSON numbers
+/// to typed integers and decimals and `null` to an empty map.
+pub fn json_to_value(json: &serde_json::Value) -> ValueContainer {
+    let value = match json {
+        serde_json::Value::Null => CoreValue::Null,
+        serde_json::Value::Bool(b) => CoreValue::from(*b),
+        serde_json::Value::Number(n) => {
+            if let Some(i) = n.as_i64() {
+                CoreValue::Integer(Integer::from(i))
+            } else if let Some(u) = n.as_u64() {
+                CoreValue::Integer(Integer::from(i128::from(u)))
+            } else {
+                CoreValue::Decimal(Decimal::from(n.as_f64().unwrap_or(f64::NAN)))
+            }
+        }
+        serde_json::Value::String(s) => CoreValue::from(s.clone()),
+        serde_json::Value::Array(items) => {
+            CoreValue::List(List::new(items.iter().map(json_to_value).collect()))
+        }
+        serde_json::Value::Object(entries) => CoreValue::Map(Map::from(
+            entries
+                .iter()
+                .map(|(key, value)| (key.clone(), json_to_value(value)))
+                .collect::<Vec<_>>(),
+        )),
+    };
+    ValueContainer::from(Value::from(value))
+}
+
+/// Prepares a value for the serde deserializer of datex-core, which does not support
+/// all DATEX values: references are replaced by their current value and
+/// untyped decimals are converted to 64-bit floats.
+/// Returns an error message for values that have no JSON representation.
+pub fn json_compatible_value(value: &ValueContainer) -> Result<ValueContainer, String> {
+    let value = value.to_value().borrow().clone();
+    let value = match &*value {
+        CoreValue::Decimal(d) if d.is_finite() => {
+            CoreValue::TypedDecimal(TypedDecimal::from(d.into_f64()))
+        }
+        CoreValue::Decimal(d) => return Err(format!("{d} can not be represented in JSON")),
+        CoreValue::TypedDecimal(d) if !d.is_finite() => {
+            return Err(format!("{d} can not be represented in JSON"));
+        }
+        CoreValue::Type(t) => return Err(format!("type {t} can not be represented in JSON")),
+        CoreValue::List(list) => CoreValue::List(List::new(
+            list.iter()
+                .map(json_compatible_value)
+                .collect::<Result<Vec<_>, _>>()?,
+        )),
+        CoreValue::Map(map) => CoreValue::Map(Map::from(
+            map.clone()
+                .into_iter()
+                .map(|(key, value)| Ok((ValueContainer::from(key), json_compatible_value(&value)?)))
+                .collect::<Result<Vec<_>, String>>()?,
+        )),
+        other => other.clone(),
+    };
+    Ok(ValueContainer::from(Value::from(value)))
+}
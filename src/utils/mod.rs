@@ -1 +1,9 @@
-pub mod config;
\ No newline at end of file
+pub mod config;
+pub mod diagnostics;
+pub mod dxb;
+pub mod errors;
+pub mod execution;
+pub mod files;
+pub mod json;
+pub mod output;
+pub mod static_value;
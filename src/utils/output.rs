@@ -0,0 +1,120 @@
+use crate::utils::json::json_compatible_value;
+use clap::ValueEnum;
+use datex_core::compiler::compile_value;
+use datex_core::compiler::error::CompilerError;
+use datex_core::decompiler::{DecompileOptions, Formatting, decompile_value};
+use datex_core::serde::deserializer::from_value_container;
+use datex_core::serde::error::DeserializationError;
+use datex_core::values::value_container::ValueContainer;
+use std::fmt::Display;
+use std::io::IsTerminal;
+
+/// Output format for values printed by the CLI
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    /// colorized multiline DATEX
+    #[default]
+    Dx,
+    /// uncolored DATEX on a single line
+    DxCompact,
+    /// JSON (only for values that can be represented in JSON)
+    Json,
+    /// hex encoded DXB bytecode of the value
+    DxbHex,
+}
+
+#[derive(Debug)]
+pub enum OutputError {
+    CompilerError(CompilerError),
+    DeserializationError(DeserializationError),
+    JsonError(serde_json::Error),
+    /// the value has no representation in the output format
+    UnsupportedValue(String),
+}
+
+impl From<CompilerError> for OutputError {
+    fn from(err: CompilerError) -> Self {
+        OutputError::CompilerError(err)
+    }
+}
+impl From<DeserializationError> for OutputError {
+    fn from(err: DeserializationError) -> Self {
+        OutputError::DeserializationError(err)
+    }
+}
+impl From<serde_json::Error> for OutputError {
+    fn from(err: serde_json::Error) -> Self {
+        OutputError::JsonError(err)
+    }
+}
+
+impl Display for OutputError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OutputError::CompilerError(e) => write!(f, "{e}"),
+            OutputError::DeserializationError(e) => write!(f, "{e}"),
+            OutputError::JsonError(e) => write!(f, "{e}"),
+            OutputError::UnsupportedValue(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+/// Renders a value in the given output format
+pub fn format_value(value: &ValueContainer, format: OutputFormat) -> Result<String, OutputError> {
+    Ok(match format {
+        OutputFormat::Dx => decompile_value(value, DecompileOptions::colorized()),
+        OutputFormat::DxCompact => compact_source(value),
+        OutputFormat::Json => {
+            let value = json_compatible_value(value).map_err(OutputError::UnsupportedValue)?;
+            let json: serde_json::Value = from_value_container(value)?;
+            serde_json::to_string_pretty(&json)?
+        }
+        OutputFormat::DxbHex => compile_value(value)?
+            .iter()
+            .map(|b| format!("{b:02x}"))
+            .collect::<String>(),
+    })
+}
+
+/// Returns the source code for a value on a single line without colors,
+/// as used for `--format dx-compact` and in diagnostics.
+pub fn compact_source(value: &ValueContainer) -> String {
+    decompile_value(
+        value,
+        DecompileOptions {
+            formatting: Formatting::Compact,
+            ..DecompileOptions::default()
+        },
+    )
+}
+
+/// Returns the canonical source code for a static DATEX value,
+/// as written by `datex fmt`.
+pub fn canonical_source(value: &ValueContainer) -> String {
+    let source = decompile_value(
+        value,
+        DecompileOptions {
+            formatting: Formatting::multiline(),
+            ..DecompileOptions::default()
+        },
+    );
+    format!("{source}\n")
+}
+
+/// Prints an error to stderr, colorized if stderr is a terminal
+pub fn print_error(err: &(impl Display + ?Sized)) {
+    if std::io::stderr().is_terminal() {
+        eprintln!("\x1b[31m{err}\x1b[0m");
+    } else {
+        eprintln!("{err}");
+    }
+}
+
+/// Prints a warning to stderr, colorized if stderr is a terminal
+pub fn print_warning(warning: &(impl Display + ?Sized)) {
+    if std::io::stderr().is_terminal() {
+        eprintln!("\x1b[33m{warning}\x1b[0m");
+    } else {
+        eprintln!("{warning}");
+    }
+}
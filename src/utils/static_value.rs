@@ -0,0 +1,87 @@
+use crate::utils::diagnostics::{Diagnostic, compiler_error_diagnostics, format_compiler_errors};
+use crate::utils::dxb::{DecompileError, read_dxb_body};
+use crate::utils::errors::{CommandError, EXIT_CODE_COMPILER_ERROR, EXIT_CODE_ERROR};
+use crate::utils::json::json_to_value;
+use datex_core::compiler::error::CompilerError;
+use datex_core::compiler::extract_static_value_from_script;
+use datex_core::decompiler::{DecompileOptions, decompile_body};
+use datex_core::values::value_container::ValueContainer;
+use std::fmt::Display;
+use std::fs;
+use std::path::Path;
+
+#[derive(Debug)]
+pub enum LoadError {
+    IOError(std::io::Error),
+    JsonError(serde_json::Error),
+    DecompileError(DecompileError),
+    CompilerError(Vec<Diagnostic>),
+    NonStaticValue,
+}
+
+impl CommandError for LoadError {
+    fn exit_code(&self) -> i32 {
+        match self {
+            LoadError::CompilerError(_) => EXIT_CODE_COMPILER_ERROR,
+            _ => EXIT_CODE_ERROR,
+        }
+    }
+}
+
+impl Display for LoadError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LoadError::IOError(e) => write!(f, "[IO Error] {e}"),
+            LoadError::JsonError(e) => write!(f, "[JSON Error] {e}"),
+            LoadError::DecompileError(e) => write!(f, "{e}"),
+            LoadError::CompilerError(diagnostics) => {
+                write!(f, "{}", format_compiler_errors(diagnostics))
+            }
+            LoadError::NonStaticValue => write!(
+                f,
+                "[Compiler Error] the file does not contain a static value"
+            ),
+        }
+    }
+}
+
+impl From<std::io::Error> for LoadError {
+    fn from(err: std::io::Error) -> Self {
+        LoadError::IOError(err)
+    }
+}
+impl From<serde_json::Error> for LoadError {
+    fn from(err: serde_json::Error) -> Self {
+        LoadError::JsonError(err)
+    }
+}
+impl From<DecompileError> for LoadError {
+    fn from(err: DecompileError) -> Self {
+        LoadError::DecompileError(err)
+    }
+}
+
+/// Loads a static value from a `.dx`, `.dxb` or `.json` file, or DATEX from stdin
+/// if the path is `-`. JSON can also be read from stdin, since it is valid DATEX.
+pub fn load_static_value(path: &Path) -> Result<ValueContainer, LoadError> {
+    let source = match path.extension().and_then(|ext| ext.to_str()) {
+        Some("json") => {
+            let json: serde_json::Value = serde_json::from_str(&fs::read_to_string(path)?)?;
+            return Ok(json_to_value(&json));
+        }
+        Some("dxb") => {
+            let body = read_dxb_body(path)?;
+            decompile_body(&body, DecompileOptions::default())
+                .map_err(|e| DecompileError::DecompilerError(e.to_string()))?
+        }
+        _ if path.as_os_str() == "-" => std::io::read_to_string(std::io::stdin())?,
+        _ => fs::read_to_string(path)?,
+    };
+    match extract_static_value_from_script(&source) {
+        Ok(Some(value)) => Ok(value),
+        Ok(None) | Err(CompilerError::NonStaticValue) => Err(LoadError::NonStaticValue),
+        Err(e) => Err(LoadError::CompilerError(compiler_error_diagnostics(
+            &e, path, &source,
+        ))),
+    }
+}
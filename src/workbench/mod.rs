@@ -3,6 +3,7 @@ use datex_core::runtime::Runtime;
 use std::io;
 
 mod views;
+#[allow(clippy::module_inception)]
 mod workbench;
 
 pub async fn start_workbench(runtime: Runtime) -> io::Result<()> {